@@ -0,0 +1,421 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::grid::Grid;
+use crate::ParseError;
+
+/// A cell position in `N` dimensions
+pub type Cell<const N: usize> = [isize; N];
+
+/// Which neighbour counts bring a dead cell to life and which keep a live one
+/// alive, written in the usual `B3/S23` notation (Conway's Game of Life)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl Rule {
+    /// Panics if `birth` contains 0, since an infinite space would fill up at once
+    pub fn new(birth: &[usize], survival: &[usize]) -> Rule {
+        assert!(!birth.contains(&0), "B0 rules are not supported");
+
+        Rule {
+            birth: birth.to_vec(),
+            survival: survival.to_vec(),
+        }
+    }
+
+    pub fn conway() -> Rule {
+        Rule::new(&[3], &[2, 3])
+    }
+
+    /// Whether a cell is alive next generation, given whether it is now and
+    /// how many of its neighbours are
+    pub fn next(&self, alive: bool, neighbours: usize) -> bool {
+        if alive {
+            self.survival.contains(&neighbours)
+        } else {
+            self.birth.contains(&neighbours)
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    /// `B<digits>/S<digits>`, e.g. `B3/S23`; each digit is one count
+    fn from_str(s: &str) -> Result<Rule, ParseError> {
+        let counts = |part: &str, prefix: char| -> Result<Vec<usize>, ParseError> {
+            let digits = part
+                .strip_prefix(prefix)
+                .ok_or_else(|| ParseError::at(s, part, format!("`{}`", prefix)))?;
+
+            digits
+                .char_indices()
+                .map(|(i, c)| {
+                    c.to_digit(10)
+                        .map(|d| d as usize)
+                        .ok_or_else(|| ParseError::at(s, &digits[i..i + c.len_utf8()], "a digit"))
+                })
+                .collect()
+        };
+
+        let (b, survival) = crate::parse::split_once_at(s, s, "/")?;
+        let birth = counts(b, 'B')?;
+        let survival = counts(survival, 'S')?;
+
+        if birth.contains(&0) {
+            return Err(ParseError::at(s, b, "a birth rule without 0"));
+        }
+
+        Ok(Rule { birth, survival })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |c: &[usize]| c.iter().map(|n| n.to_string()).collect::<String>();
+
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
+
+/// The 3^N - 1 offsets to every cell touching the origin, diagonals included
+pub fn moore<const N: usize>() -> Vec<Cell<N>> {
+    let mut offsets = vec![[0; N]];
+
+    for axis in 0..N {
+        offsets = offsets
+            .into_iter()
+            .flat_map(|o| {
+                (-1..=1).map(move |d| {
+                    let mut o = o;
+                    o[axis] = d;
+                    o
+                })
+            })
+            .collect();
+    }
+
+    offsets.retain(|o| o.iter().any(|&d| d != 0));
+    offsets
+}
+
+/// The live cells of a `#`/`.` map, placed on the plane where every axis
+/// after the first two is 0; `x` runs along rows and `y` down the map
+pub fn from_plane<const N: usize>(input: &str) -> Result<Vec<Cell<N>>, ParseError> {
+    assert!(N >= 2, "a plane needs at least 2 dimensions");
+
+    let plane = Grid::parse(input, "`.` or `#`", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+
+    let cells = plane
+        .positions()
+        .filter(|&p| plane[p])
+        .map(|(r, c)| {
+            let mut cell = [0; N];
+            cell[0] = c as isize;
+            cell[1] = r as isize;
+            cell
+        })
+        .collect();
+
+    Ok(cells)
+}
+
+fn add<const N: usize>(a: &Cell<N>, b: &Cell<N>) -> Cell<N> {
+    let mut out = *a;
+    for (o, d) in out.iter_mut().zip(b.iter()) {
+        *o += d;
+    }
+    out
+}
+
+/// How the live cells of an automaton are stored
+pub trait Backend<const N: usize>: Sized {
+    fn from_active<I: IntoIterator<Item = Cell<N>>>(cells: I) -> Self;
+
+    fn is_active(&self, cell: &Cell<N>) -> bool;
+
+    fn active(&self) -> Vec<Cell<N>>;
+
+    fn count(&self) -> usize;
+
+    /// The next generation
+    fn step(&self, rule: &Rule, offsets: &[Cell<N>]) -> Self;
+}
+
+/// Only the live cells, in a `HashSet`; suits patterns that spread thinly
+#[derive(Debug, Clone, Default)]
+pub struct Sparse<const N: usize> {
+    active: HashSet<Cell<N>>,
+}
+
+impl<const N: usize> Backend<N> for Sparse<N> {
+    fn from_active<I: IntoIterator<Item = Cell<N>>>(cells: I) -> Self {
+        Sparse {
+            active: cells.into_iter().collect(),
+        }
+    }
+
+    fn is_active(&self, cell: &Cell<N>) -> bool {
+        self.active.contains(cell)
+    }
+
+    fn active(&self) -> Vec<Cell<N>> {
+        self.active.iter().copied().collect()
+    }
+
+    fn count(&self) -> usize {
+        self.active.len()
+    }
+
+    fn step(&self, rule: &Rule, offsets: &[Cell<N>]) -> Self {
+        // Only live cells and their neighbours can be alive next time
+        let mut counts: HashMap<Cell<N>, usize> = HashMap::new();
+
+        for cell in &self.active {
+            counts.entry(*cell).or_insert(0);
+
+            for o in offsets {
+                *counts.entry(add(cell, o)).or_insert(0) += 1;
+            }
+        }
+
+        let active = counts
+            .into_iter()
+            .filter(|(cell, n)| rule.next(self.active.contains(cell), *n))
+            .map(|(cell, _)| cell)
+            .collect();
+
+        Sparse { active }
+    }
+}
+
+/// Every cell of the bounding box of the live cells, in a flat `Vec`; suits
+/// dense patterns in few dimensions
+#[derive(Debug, Clone)]
+pub struct Dense<const N: usize> {
+    min: Cell<N>,
+    size: [usize; N],
+    cells: Vec<bool>,
+}
+
+impl<const N: usize> Dense<N> {
+    fn index(&self, cell: &Cell<N>) -> Option<usize> {
+        let mut idx = 0;
+
+        for ((&c, &min), &size) in cell.iter().zip(&self.min).zip(&self.size) {
+            let d = c - min;
+            if d < 0 || d as usize >= size {
+                return None;
+            }
+            idx = idx * size + d as usize;
+        }
+
+        Some(idx)
+    }
+
+    fn cell(&self, mut idx: usize) -> Cell<N> {
+        let mut cell = self.min;
+
+        for axis in (0..N).rev() {
+            cell[axis] += (idx % self.size[axis]) as isize;
+            idx /= self.size[axis];
+        }
+
+        cell
+    }
+}
+
+impl<const N: usize> Backend<N> for Dense<N> {
+    fn from_active<I: IntoIterator<Item = Cell<N>>>(cells: I) -> Self {
+        let cells: Vec<_> = cells.into_iter().collect();
+
+        let mut min = [0; N];
+        let mut size = [0; N];
+
+        if !cells.is_empty() {
+            for axis in 0..N {
+                let lo = cells.iter().map(|c| c[axis]).min().unwrap_or(0);
+                let hi = cells.iter().map(|c| c[axis]).max().unwrap_or(0);
+
+                min[axis] = lo;
+                size[axis] = (hi - lo + 1) as usize;
+            }
+        }
+
+        let mut dense = Dense {
+            min,
+            size,
+            cells: vec![false; size.iter().product()],
+        };
+
+        for cell in &cells {
+            if let Some(i) = dense.index(cell) {
+                dense.cells[i] = true;
+            }
+        }
+
+        dense
+    }
+
+    fn is_active(&self, cell: &Cell<N>) -> bool {
+        self.index(cell).map(|i| self.cells[i]).unwrap_or(false)
+    }
+
+    fn active(&self) -> Vec<Cell<N>> {
+        (0..self.cells.len())
+            .filter(|&i| self.cells[i])
+            .map(|i| self.cell(i))
+            .collect()
+    }
+
+    fn count(&self) -> usize {
+        self.cells.iter().filter(|&&c| c).count()
+    }
+
+    fn step(&self, rule: &Rule, offsets: &[Cell<N>]) -> Self {
+        let active = self.active();
+        if active.is_empty() {
+            return self.clone();
+        }
+
+        // Grow the box by the reach of the neighbourhood, count neighbours
+        // into it, then shrink back to whatever is alive
+        let reach = offsets
+            .iter()
+            .flat_map(|o| o.iter().map(|d| d.unsigned_abs()))
+            .max()
+            .unwrap_or(0);
+
+        let mut grown = Dense {
+            min: self.min,
+            size: self.size,
+            cells: Vec::new(),
+        };
+        for axis in 0..N {
+            grown.min[axis] -= reach as isize;
+            grown.size[axis] += 2 * reach;
+        }
+
+        let mut counts = vec![0_usize; grown.size.iter().product()];
+
+        for cell in &active {
+            for o in offsets {
+                if let Some(i) = grown.index(&add(cell, o)) {
+                    counts[i] += 1;
+                }
+            }
+        }
+
+        let next = counts
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| (grown.cell(i), n))
+            .filter(|(cell, n)| rule.next(self.is_active(cell), *n))
+            .map(|(cell, _)| cell);
+
+        Dense::from_active(next)
+    }
+}
+
+/// A Conway-style cellular automaton in `N` dimensions
+#[derive(Debug, Clone)]
+pub struct Automaton<const N: usize, B: Backend<N> = Sparse<N>> {
+    rule: Rule,
+    offsets: Vec<Cell<N>>,
+    cells: B,
+}
+
+impl<const N: usize, B: Backend<N>> Automaton<N, B> {
+    /// An automaton where every touching cell, diagonals included, is a neighbour
+    pub fn new<I: IntoIterator<Item = Cell<N>>>(rule: Rule, active: I) -> Self {
+        Automaton::with_neighbourhood(rule, moore::<N>(), active)
+    }
+
+    /// An automaton whose neighbours are the given offsets from each cell
+    pub fn with_neighbourhood<I: IntoIterator<Item = Cell<N>>>(
+        rule: Rule,
+        offsets: Vec<Cell<N>>,
+        active: I,
+    ) -> Self {
+        Automaton {
+            rule,
+            offsets,
+            cells: B::from_active(active),
+        }
+    }
+
+    pub fn step(&mut self) {
+        self.cells = self.cells.step(&self.rule, &self.offsets);
+    }
+
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    pub fn is_active(&self, cell: &Cell<N>) -> bool {
+        self.cells.is_active(cell)
+    }
+
+    pub fn active(&self) -> Vec<Cell<N>> {
+        self.cells.active()
+    }
+
+    pub fn count(&self) -> usize {
+        self.cells.count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_test() {
+        let rule: Rule = "B3/S23".parse().unwrap();
+        assert_eq!(rule, Rule::conway());
+        assert_eq!(rule.to_string(), "B3/S23");
+        assert!(rule.next(false, 3) && rule.next(true, 2) && !rule.next(true, 4));
+
+        assert_eq!(
+            "B3/S2x".parse::<Rule>().unwrap_err().to_string(),
+            "line 1, column 6: expected a digit, found `x`"
+        );
+        assert!("B03/S23".parse::<Rule>().is_err());
+        assert!("B3S23".parse::<Rule>().is_err());
+
+        assert_eq!(moore::<2>().len(), 8);
+        assert_eq!(moore::<4>().len(), 80);
+    }
+
+    #[test]
+    fn blinker_test() {
+        let cells = from_plane::<2>(".#.\n.#.\n.#.\n").unwrap();
+
+        let mut sparse: Automaton<2> = Automaton::new(Rule::conway(), cells.clone());
+        let mut dense: Automaton<2, Dense<2>> = Automaton::new(Rule::conway(), cells);
+
+        sparse.step();
+        dense.step();
+
+        for a in [&sparse.active(), &dense.active()].iter() {
+            let mut a = a.to_vec();
+            a.sort_unstable();
+            assert_eq!(a, [[0, 1], [1, 1], [2, 1]]);
+        }
+
+        sparse.run(3);
+        dense.run(3);
+        assert!(sparse.is_active(&[1, 0]) && dense.is_active(&[1, 2]));
+        assert_eq!((sparse.count(), dense.count()), (3, 3));
+    }
+}
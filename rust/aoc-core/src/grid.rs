@@ -0,0 +1,389 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::ParseError;
+
+/// A `(row, column)` position, with row 0 at the top
+pub type Pos = (usize, usize);
+
+/// A `(row, column)` step
+pub type Dir = (isize, isize);
+
+/// Up, right, down and left
+pub const DIRS4: [Dir; 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// The 4 orthogonal steps and the 4 diagonals, clockwise from up
+pub const DIRS8: [Dir; 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// What happens when a step leaves the grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bounds {
+    /// There is nothing past the edge
+    Clamp,
+    /// The grid repeats, so leaving one edge re-enters at the opposite one
+    Wrap,
+}
+
+/// One of the 8 symmetries of a square: an optional mirror that reverses
+/// every row, followed by `turns` anticlockwise quarter turns
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub flip: bool,
+    pub turns: u8,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::new(false, 0),
+        Transform::new(false, 1),
+        Transform::new(false, 2),
+        Transform::new(false, 3),
+        Transform::new(true, 0),
+        Transform::new(true, 1),
+        Transform::new(true, 2),
+        Transform::new(true, 3),
+    ];
+
+    pub const fn new(flip: bool, turns: u8) -> Transform {
+        Transform { flip, turns }
+    }
+}
+
+/// A rectangular grid of cells stored row by row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Panics if `cells` does not hold exactly `width * height` cells
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "grid size mismatch");
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parse a character map, one row per line, turning each character into a
+    /// cell with `cell`. Rows must be non-empty and all the same width.
+    pub fn parse<F>(input: &str, expected: &str, cell: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        Grid::parse_within(input, input, expected, cell)
+    }
+
+    /// Like `parse`, for a map that is only part of `input`, so that errors
+    /// point into the whole input
+    pub fn parse_within<F>(
+        input: &str,
+        map: &str,
+        expected: &str,
+        mut cell: F,
+    ) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for line in map.lines() {
+            let mut row_width = 0;
+
+            for (i, c) in line.char_indices() {
+                let value = cell(c)
+                    .ok_or_else(|| ParseError::at(input, &line[i..i + c.len_utf8()], expected))?;
+
+                cells.push(value);
+                row_width += 1;
+            }
+
+            match width {
+                None if row_width == 0 => return Err(ParseError::at(input, line, "a row")),
+                None => width = Some(row_width),
+                Some(w) if w != row_width => {
+                    let expected = format!("a row of width {}", w);
+                    return Err(ParseError::at(input, line, expected));
+                }
+                Some(_) => (),
+            }
+
+            height += 1;
+        }
+
+        let width = width.ok_or_else(|| ParseError::within(input, map, map.len(), "a row"))?;
+
+        Ok(Grid::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (r, c): Pos) -> Option<&T> {
+        if r < self.height && c < self.width {
+            self.cells.get(r * self.width + c)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (r, c): Pos) -> Option<&mut T> {
+        if r < self.height && c < self.width {
+            self.cells.get_mut(r * self.width + c)
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, c: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(c).step_by(self.width.max(1))
+    }
+
+    /// Every cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Where a single step of `dir` from `pos` lands, if it stays on the grid
+    pub fn step(&self, (r, c): Pos, (dr, dc): Dir, bounds: Bounds) -> Option<Pos> {
+        if self.cells.is_empty() {
+            return None;
+        }
+
+        let (r, c) = (r as isize + dr, c as isize + dc);
+        let (h, w) = (self.height as isize, self.width as isize);
+
+        match bounds {
+            Bounds::Clamp if (0..h).contains(&r) && (0..w).contains(&c) => {
+                Some((r as usize, c as usize))
+            }
+            Bounds::Clamp => None,
+            Bounds::Wrap => Some((r.rem_euclid(h) as usize, c.rem_euclid(w) as usize)),
+        }
+    }
+
+    /// The orthogonal neighbours of `pos` that are on the grid
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS4
+            .iter()
+            .filter_map(move |&d| self.step(pos, d, Bounds::Clamp))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that are on the grid
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS8
+            .iter()
+            .filter_map(move |&d| self.step(pos, d, Bounds::Clamp))
+    }
+
+    /// The positions reached by repeatedly stepping `dir` from `pos`, not
+    /// including `pos` itself. With `Bounds::Wrap` this never ends.
+    pub fn ray(&self, pos: Pos, dir: Dir, bounds: Bounds) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            pos,
+            dir,
+            bounds,
+        }
+    }
+
+    /// Draw the grid one row per line, with `f` choosing each cell's character
+    pub fn render<F: FnMut(&T) -> char>(&self, mut f: F) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut f).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// The `width` by `height` region whose top left corner is at `pos`.
+    /// Panics if the region does not fit.
+    pub fn crop(&self, (r, c): Pos, width: usize, height: usize) -> Grid<T> {
+        assert!(r + height <= self.height && c + width <= self.width);
+
+        let cells = (r..r + height)
+            .flat_map(|r| self.row(r)[c..c + width].iter().cloned())
+            .collect();
+
+        Grid::new(width, height, cells)
+    }
+
+    /// Mirror left to right
+    pub fn flip(&self) -> Grid<T> {
+        let cells = self
+            .rows()
+            .flat_map(|row| row.iter().rev().cloned())
+            .collect();
+
+        Grid::new(self.width, self.height, cells)
+    }
+
+    /// Turn a quarter anticlockwise, so the right-hand column becomes the top row
+    pub fn rotate_left(&self) -> Grid<T> {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|c| self.column(c).cloned())
+            .collect();
+
+        Grid::new(self.height, self.width, cells)
+    }
+
+    pub fn transform(&self, t: Transform) -> Grid<T> {
+        let mut out = if t.flip { self.flip() } else { self.clone() };
+
+        for _ in 0..t.turns % 4 {
+            out = out.rotate_left();
+        }
+
+        out
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (r, c): Pos) -> &T {
+        assert!(c < self.width, "column {} out of range", c);
+        &self.cells[r * self.width + c]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (r, c): Pos) -> &mut T {
+        assert!(c < self.width, "column {} out of range", c);
+        &mut self.cells[r * self.width + c]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterator returned by `Grid::ray`
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Pos,
+    dir: Dir,
+    bounds: Bounds,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = Pos;
+
+    fn next(&mut self) -> Option<Pos> {
+        self.pos = self.grid.step(self.pos, self.dir, self.bounds)?;
+        Some(self.pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parse_test() {
+        let g = digits("123\n456\n");
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[(1, 2)], 6);
+        assert_eq!(g.to_string(), "123\n456");
+
+        let e = Grid::parse("123\n4x6\n", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 2: expected a digit, found `x`"
+        );
+
+        let e = Grid::parse("123\n45\n", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(e.expected, "a row of width 3");
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let g = digits("123\n456\n789\n");
+        assert_eq!(g.neighbours4((0, 0)).collect::<Vec<_>>(), [(0, 1), (1, 0)]);
+        assert_eq!(g.neighbours8((1, 1)).count(), 8);
+        assert_eq!(g.neighbours8((2, 2)).count(), 3);
+
+        let ray: Vec<_> = g.ray((0, 0), (1, 1), Bounds::Clamp).collect();
+        assert_eq!(ray, [(1, 1), (2, 2)]);
+
+        let ray: Vec<_> = g.ray((0, 0), (1, 2), Bounds::Wrap).take(3).collect();
+        assert_eq!(ray, [(1, 2), (2, 1), (0, 0)]);
+    }
+
+    #[test]
+    fn transform_test() {
+        let g = digits("12\n34\n56\n");
+        assert_eq!(g.rotate_left().to_string(), "246\n135");
+        assert_eq!(g.flip().to_string(), "21\n43\n65");
+        assert_eq!(g.transform(Transform::new(false, 4)), g);
+
+        let all: Vec<_> = Transform::ALL.iter().map(|&t| g.transform(t)).collect();
+        for (i, a) in all.iter().enumerate() {
+            assert!(all[i + 1..].iter().all(|b| a != b));
+        }
+
+        assert_eq!(
+            digits("123\n456\n789\n").crop((1, 1), 2, 2).to_string(),
+            "56\n89"
+        );
+    }
+}
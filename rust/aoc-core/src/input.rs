@@ -0,0 +1,66 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::parse::parse_at;
+use crate::Result;
+
+pub fn read_stdin() -> Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+pub fn read_file<P: AsRef<Path>>(path: P) -> Result<String> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e).into())
+}
+
+/// Parse every line of the input as a `T`, e.g. a list of numbers
+pub fn parse_lines<T: FromStr>(input: &str) -> Result<Vec<T>> {
+    let expected = format!("a {}", std::any::type_name::<T>());
+
+    let mut out = Vec::new();
+    for line in input.lines() {
+        out.push(parse_at(input, line, &expected)?);
+    }
+    Ok(out)
+}
+
+/// Split the input into blank-line separated groups of lines
+pub fn groups(input: &str) -> Vec<Vec<&str>> {
+    input
+        .lines()
+        .fold(vec![Vec::new()], |mut acc, line| {
+            if line.is_empty() {
+                acc.push(Vec::new());
+            } else if let Some(g) = acc.last_mut() {
+                g.push(line);
+            }
+            acc
+        })
+        .into_iter()
+        .filter(|g| !g.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_lines_test() {
+        let x: Vec<u32> = parse_lines("1\n22\n333\n").unwrap();
+        assert_eq!(x, vec![1, 22, 333]);
+
+        let e = parse_lines::<u32>("1\nx\n").unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 1: expected a u32, found `x`");
+    }
+
+    #[test]
+    fn groups_test() {
+        let g = groups("a\nb\n\nc\n\n\nd\n");
+        assert_eq!(g, vec![vec!["a", "b"], vec!["c"], vec!["d"]]);
+    }
+}
@@ -0,0 +1,38 @@
+//! Shared harness for the daily solutions: the `Solution` trait every day
+//! implements, a common error type, helpers for loading and parsing puzzle input,
+//! a 2D `Grid` for the days whose input is a character map and an
+//! N-dimensional cellular `automaton`.
+
+pub mod automaton;
+pub mod grid;
+pub mod input;
+pub mod parse;
+mod solution;
+
+pub use grid::Grid;
+pub use parse::ParseError;
+pub use solution::{run, Answer, Report, Solution};
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Entry point for a day's own binary: reads the puzzle from stdin and
+/// prints both answers along with the time each stage took.
+pub fn main<S: Solution>() -> Result<()> {
+    let input = input::read_stdin()?;
+    let report = run::<S>(&input)?;
+
+    eprintln!("parse elapsed {:?}", report.parse);
+
+    for (i, part) in [&report.part1, &report.part2].iter().enumerate() {
+        match part {
+            Ok(answer) => {
+                println!("part {} solution: {}", i + 1, answer.value);
+                eprintln!("elapsed {:?}", answer.elapsed);
+            }
+            Err(e) => eprintln!("part {} failed: {}", i + 1, e),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A malformed input, pointing at where parsing stopped and what it was looking for
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    /// The offending text; empty at the end of a line or of the input
+    pub text: String,
+    pub expected: String,
+    /// Whether the offending text is empty because the whole input ran out
+    pub end_of_input: bool,
+}
+
+impl ParseError {
+    pub fn new<T: Into<String>, E: Into<String>>(
+        line: usize,
+        column: usize,
+        text: T,
+        expected: E,
+    ) -> ParseError {
+        ParseError {
+            line,
+            column,
+            text: text.into(),
+            expected: expected.into(),
+            end_of_input: false,
+        }
+    }
+
+    /// The same error, but at byte `offset` of `input`
+    fn ending(mut self, input: &str, offset: usize) -> ParseError {
+        self.end_of_input = self.text.is_empty() && offset == input.len();
+        self
+    }
+
+    /// Blame `fragment`, which must be a slice of `input`
+    pub fn at<E: Into<String>>(input: &str, fragment: &str, expected: E) -> ParseError {
+        let offset = offset_of(input, fragment);
+        let (line, column) = line_column(input, offset);
+        ParseError::new(line, column, fragment, expected).ending(input, offset)
+    }
+
+    /// Blame byte `offset` of `fragment`, a slice of `input`, e.g. where a
+    /// sub-parser that only saw `fragment` gave up
+    pub fn within<E: Into<String>>(
+        input: &str,
+        fragment: &str,
+        offset: usize,
+        expected: E,
+    ) -> ParseError {
+        ParseError::at_offset(input, offset_of(input, fragment) + offset, expected)
+    }
+
+    /// Blame whatever starts at byte `offset` of `input`, up to the end of its line
+    pub fn at_offset<E: Into<String>>(input: &str, offset: usize, expected: E) -> ParseError {
+        let offset = offset.min(input.len());
+        let rest = &input[offset..];
+        let text = rest.lines().next().unwrap_or("");
+
+        let (line, column) = line_column(input, offset);
+        ParseError::new(line, column, text, expected).ending(input, offset)
+    }
+
+    /// Blame the end of `input`, e.g. when a section is missing
+    pub fn at_end<E: Into<String>>(input: &str, expected: E) -> ParseError {
+        ParseError::at_offset(input, input.len(), expected)
+    }
+}
+
+/// Byte offset of `fragment` within `input`, or the end of `input` if it is not a slice of it
+fn offset_of(input: &str, fragment: &str) -> usize {
+    (fragment.as_ptr() as usize)
+        .checked_sub(input.as_ptr() as usize)
+        .filter(|&o| o <= input.len())
+        .unwrap_or(input.len())
+}
+
+fn line_column(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    (line, before[line_start..].chars().count() + 1)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+
+        if self.end_of_input {
+            write!(f, "found end of input")
+        } else if self.text.is_empty() {
+            write!(f, "found end of line")
+        } else {
+            write!(f, "found `{}`", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse `fragment`, a slice of `input`, blaming it if it is not a valid `T`
+pub fn parse_at<T: FromStr>(input: &str, fragment: &str, expected: &str) -> Result<T, ParseError> {
+    fragment
+        .parse()
+        .map_err(|_| ParseError::at(input, fragment, expected))
+}
+
+/// Split `fragment` at the first `delimiter`, blaming the whole fragment if it is missing
+pub fn split_once_at<'a>(
+    input: &str,
+    fragment: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    fragment
+        .split_once(delimiter)
+        .ok_or_else(|| ParseError::at(input, fragment, format!("`{}`", delimiter)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn location_test() {
+        let input = "nop +0\nacc +1\njmp x4\n";
+        let line = input.lines().nth(2).unwrap();
+
+        let e = ParseError::at(input, &line[4..], "a signed number");
+        assert_eq!(e, ParseError::new(3, 5, "x4", "a signed number"));
+        assert_eq!(
+            e.to_string(),
+            "line 3, column 5: expected a signed number, found `x4`"
+        );
+
+        let e = ParseError::at_offset(input, 7, "an instruction");
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "acc +1"));
+
+        let e = ParseError::within(input, line, 4, "a signed number");
+        assert_eq!((e.line, e.column, e.text.as_str()), (3, 5, "x4"));
+
+        let e = ParseError::at_end(input, "a blank line");
+        assert_eq!(e.line, 4);
+        assert!(e.to_string().ends_with("found end of input"));
+
+        // A line cut short in the middle of the input ends the line, not the input
+        let short = "acc +1\nnop\njmp +2\n";
+        let nop = short.lines().nth(1).unwrap();
+        let e = ParseError::at(short, &nop[3..], "an argument");
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 4: expected an argument, found end of line"
+        );
+        let e = ParseError::at_offset(short, 10, "an argument");
+        assert!(e.to_string().ends_with("found end of line"));
+
+        assert!(parse_at::<i32>(input, &line[4..], "a number").is_err());
+        assert_eq!(split_once_at(input, line, " ").unwrap(), ("jmp", "x4"));
+    }
+}
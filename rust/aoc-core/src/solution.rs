@@ -0,0 +1,53 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use crate::Result;
+
+/// A day's puzzle. The input is parsed once and shared by both parts.
+pub trait Solution {
+    type Input<'a>;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>>;
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Part1>;
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Part2>;
+}
+
+#[derive(Debug)]
+pub struct Answer {
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+/// Answers and stage timings from running a `Solution` on one input
+#[derive(Debug)]
+pub struct Report {
+    pub parse: Duration,
+    pub part1: Result<Answer>,
+    pub part2: Result<Answer>,
+}
+
+fn timed<T: Display>(f: impl FnOnce() -> Result<T>) -> Result<Answer> {
+    let start = Instant::now();
+    let value = f()?.to_string();
+
+    Ok(Answer {
+        value,
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Parse the input and run both parts. A failing part is recorded in the
+/// report; only a parse failure aborts the run.
+pub fn run<S: Solution>(input: &str) -> Result<Report> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    Ok(Report {
+        parse,
+        part1: timed(|| S::part1(&parsed)),
+        part2: timed(|| S::part2(&parsed)),
+    })
+}
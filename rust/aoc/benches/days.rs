@@ -0,0 +1,129 @@
+//! Benchmarks the parse, part 1 and part 2 stages of every day against its committed input.
+//! Each part is timed on a freshly parsed input, so nothing a day remembers from an earlier run
+//! (day 7's memoised queries, say) makes a later iteration look faster than it is.
+//!
+//! Save a baseline with `cargo bench -p aoc -- --save-baseline <name>` and compare a later run
+//! against it with `cargo bench -p aoc -- --baseline <name>`. A single day can be selected with
+//! a filter, e.g. `cargo bench -p aoc -- day15`.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_core::{input, Solution};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+#[path = "../src/year.rs"]
+mod year;
+
+use year::DEFAULT_YEAR;
+
+/// Default-year days whose parts take tens of milliseconds or more on the real input
+const SLOW_DAYS: [u8; 7] = [11, 15, 17, 22, 23, 24, 25];
+
+/// Same layout as the runner: `aocNN` for the default year and `<year>/aocNN` for the others
+fn input_path(year: u16, day: u8) -> PathBuf {
+    let mut path: PathBuf = [env!("CARGO_MANIFEST_DIR"), ".."].iter().collect();
+
+    if year != DEFAULT_YEAR {
+        path.push(year.to_string());
+    }
+
+    path.join(format!("aoc{:02}", day)).join("input")
+}
+
+fn bench_day<S: Solution>(c: &mut Criterion, year: u16, day: u8) {
+    let raw = input::read_file(input_path(year, day)).unwrap();
+
+    let name = if year == DEFAULT_YEAR {
+        format!("day{:02}", day)
+    } else {
+        format!("{}-day{:02}", year, day)
+    };
+    let mut group = c.benchmark_group(name);
+
+    if year == DEFAULT_YEAR && SLOW_DAYS.contains(&day) {
+        group.sample_size(10);
+        group.measurement_time(Duration::from_secs(20));
+    }
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&raw)).unwrap()));
+    group.bench_function("part1", |b| {
+        b.iter_batched(
+            || S::parse(&raw).unwrap(),
+            |parsed| S::part1(black_box(&parsed)).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("part2", |b| {
+        b.iter_batched(
+            || S::parse(&raw).unwrap(),
+            |parsed| S::part2(black_box(&parsed)).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+macro_rules! bench_days {
+    ($(($year:literal, $day:literal) => $solution:ty),* $(,)?) => {
+        fn days(c: &mut Criterion) {
+            $(bench_day::<$solution>(c, $year, $day);)*
+        }
+    };
+}
+
+bench_days! {
+    (2020, 1) => aoc01::Day01,
+    (2020, 2) => aoc02::Day02,
+    (2020, 3) => aoc03::Day03,
+    (2020, 4) => aoc04::Day04,
+    (2020, 5) => aoc05::Day05,
+    (2020, 6) => aoc06::Day06,
+    (2020, 7) => aoc07::Day07,
+    (2020, 8) => aoc08::Day08,
+    (2020, 9) => aoc09::Day09,
+    (2020, 10) => aoc10::Day10,
+    (2020, 11) => aoc11::Day11,
+    (2020, 12) => aoc12::Day12,
+    (2020, 13) => aoc13::Day13,
+    (2020, 14) => aoc14::Day14,
+    (2020, 15) => aoc15::Day15,
+    (2020, 16) => aoc16::Day16,
+    (2020, 17) => aoc17::Day17,
+    (2020, 18) => aoc18::Day18,
+    (2020, 19) => aoc19::Day19,
+    (2020, 20) => aoc20::Day20,
+    (2020, 21) => aoc21::Day21,
+    (2020, 22) => aoc22::Day22,
+    (2020, 23) => aoc23::Day23,
+    (2020, 24) => aoc24::Day24,
+    (2020, 25) => aoc25::Day25,
+}
+
+/// Day 6's `AnswerSet` bitsets against the `HashSet` answers they replaced
+fn day06_sets(c: &mut Criterion) {
+    let raw = input::read_file(input_path(DEFAULT_YEAR, 6)).unwrap();
+    let mut group = c.benchmark_group("day06-sets");
+
+    group.bench_function("hashset", |b| {
+        b.iter(|| {
+            let input = black_box(raw.as_str());
+            (aoc06::part1(input).unwrap(), aoc06::part2(input).unwrap())
+        })
+    });
+    group.bench_function("bitset", |b| {
+        b.iter(|| {
+            let groups = aoc06::Day06::parse(black_box(&raw)).unwrap();
+            (
+                aoc06::Day06::part1(&groups).unwrap(),
+                aoc06::Day06::part2(&groups).unwrap(),
+            )
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, days, day06_sets);
+criterion_main!(benches);
@@ -0,0 +1,145 @@
+//! Generates one test per example file for `tests/examples.rs`.
+//!
+//! Each `aocNN/examples/NAME.txt` becomes a test named `dayNN_NAME`, and each
+//! `<year>/aocNN/examples/NAME.txt` from a later year becomes `yYEAR_dayNN_NAME`.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+#[path = "src/year.rs"]
+mod year;
+
+use year::DEFAULT_YEAR;
+
+/// The sidecar format read by `tests/examples.rs`
+#[derive(Deserialize)]
+struct Expected {
+    part1: Option<toml::Value>,
+    part2: Option<toml::Value>,
+}
+
+fn ident(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => c.to_ascii_lowercase(),
+            _ => '_',
+        })
+        .collect()
+}
+
+fn example_names(examples_dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(examples_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.unwrap().path();
+            match path.extension() {
+                Some(ext) if ext == "txt" => Some(path.file_stem()?.to_string_lossy().into_owned()),
+                _ => None,
+            }
+        })
+        .collect();
+    names.sort();
+
+    names
+}
+
+/// Whether a sidecar sets `part1` or `part2`, rather than only the
+/// commented-out lines of a scaffolded stub. A missing or malformed sidecar
+/// counts as recording answers, so its test runs and reports the problem.
+fn records_answers(sidecar: &Path) -> bool {
+    let expected = fs::read_to_string(sidecar)
+        .ok()
+        .and_then(|s| toml::from_str::<Expected>(&s).ok());
+
+    match expected {
+        Some(e) => e.part1.is_some() || e.part2.is_some(),
+        None => true,
+    }
+}
+
+fn main() {
+    let workspace: PathBuf = [env::var("CARGO_MANIFEST_DIR").unwrap(), "..".to_string()]
+        .iter()
+        .collect();
+
+    // `aoc new` registers every day crate in the workspace manifest
+    println!(
+        "cargo:rerun-if-changed={}",
+        workspace.join("Cargo.toml").display()
+    );
+
+    let mut years = vec![(DEFAULT_YEAR, String::new())];
+    for entry in fs::read_dir(&workspace).unwrap() {
+        let name = entry.unwrap().file_name().to_string_lossy().into_owned();
+        if let Ok(year) = name.parse::<u16>() {
+            years.push((year, name));
+        }
+    }
+    years.sort();
+
+    let mut tests = String::new();
+
+    for (year, year_dir) in years {
+        for day in 1..=25 {
+            let dir = if year == DEFAULT_YEAR {
+                format!("aoc{:02}", day)
+            } else {
+                format!("{}/aoc{:02}", year_dir, day)
+            };
+            let crate_dir = workspace.join(&dir);
+            let examples_dir = crate_dir.join("examples");
+
+            if !examples_dir.is_dir() {
+                // Rerun once the directory appears
+                if crate_dir.is_dir() {
+                    println!("cargo:rerun-if-changed={}", crate_dir.display());
+                }
+                continue;
+            }
+
+            println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+            let (prefix, krate) = if year == DEFAULT_YEAR {
+                (String::new(), format!("aoc{:02}", day))
+            } else {
+                (format!("y{}_", year), format!("aoc{}_{:02}", year, day))
+            };
+
+            for name in example_names(&examples_dir) {
+                let sidecar = examples_dir.join(format!("{}.toml", name));
+                println!("cargo:rerun-if-changed={}", sidecar.display());
+
+                let ignore = if records_answers(&sidecar) {
+                    String::new()
+                } else {
+                    format!("#[ignore = \"{}.toml records no answers yet\"]\n", name)
+                };
+
+                writeln!(
+                    tests,
+                    "#[test]\n{ignore}fn {prefix}day{day:02}_{ident}() {{\n    check::<{krate}::Day{day:02}>({dir:?}, {name:?});\n}}\n",
+                    ignore = ignore,
+                    prefix = prefix,
+                    day = day,
+                    ident = ident(&name),
+                    krate = krate,
+                    dir = dir,
+                    name = name,
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let out: PathBuf = [env::var("OUT_DIR").unwrap(), "examples.rs".to_string()]
+        .iter()
+        .collect();
+    fs::write(out, tests).unwrap();
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/year.rs");
+}
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use aoc_core::{input, Result};
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DayAnswers {
+    pub part1: Option<toml::Value>,
+    pub part2: Option<toml::Value>,
+}
+
+/// Recorded answers for the committed inputs, keyed by year and `dayNN`
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    years: HashMap<String, HashMap<String, DayAnswers>>,
+}
+
+impl Answers {
+    pub fn default_path() -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "..", "answers.toml"]
+            .iter()
+            .collect()
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Answers> {
+        let raw = input::read_file(path)?;
+        Ok(toml::from_str(&raw)?)
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&DayAnswers> {
+        self.years
+            .get(&year.to_string())?
+            .get(&format!("day{:02}", day))
+    }
+}
+
+/// Render a recorded answer the same way a `Solution` displays its value
+pub fn expected(v: &toml::Value) -> String {
+    match v {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
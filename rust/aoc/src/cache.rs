@@ -0,0 +1,268 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use aoc_core::{input, Result};
+
+use crate::client::{Client, Outcome, Verdict};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Everything submitted for one day, plus the earliest time the server will accept another answer
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SubmissionLog {
+    /// Seconds since the unix epoch
+    pub wait_until: Option<u64>,
+    #[serde(default, rename = "submission")]
+    pub submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Why `answer` should not be sent for `part`, if a previous submission already tells us
+    pub fn rejection(&self, part: u8, answer: &str) -> Option<String> {
+        let previous = self.submissions.iter().filter(|s| s.part == part);
+
+        for s in previous {
+            if s.outcome == Outcome::Correct {
+                return Some(format!(
+                    "part {} was already solved with `{}`",
+                    part, s.answer
+                ));
+            }
+
+            if s.answer == answer {
+                return Some(format!(
+                    "`{}` was already submitted and was {}",
+                    answer, s.outcome
+                ));
+            }
+
+            if let (Ok(previous), Ok(answer)) = (s.answer.parse::<i64>(), answer.parse::<i64>()) {
+                if s.outcome == Outcome::TooHigh && answer >= previous {
+                    return Some(format!("`{}` was already too high", previous));
+                }
+                if s.outcome == Outcome::TooLow && answer <= previous {
+                    return Some(format!("`{}` was already too low", previous));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Downloaded inputs and submission history, stored as `<root>/<year>/dayNN/`
+pub struct Cache {
+    root: PathBuf,
+}
+
+impl Cache {
+    pub fn new<P: AsRef<Path>>(root: P) -> Cache {
+        Cache {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// `AOC_CACHE_DIR`, defaulting to `rust/.cache`
+    pub fn from_env() -> Cache {
+        match env::var_os("AOC_CACHE_DIR") {
+            Some(dir) => Cache::new(dir),
+            None => Cache::new(
+                [env!("CARGO_MANIFEST_DIR"), "..", ".cache"]
+                    .iter()
+                    .collect::<PathBuf>(),
+            ),
+        }
+    }
+
+    fn day_dir(&self, year: u16, day: u8) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("day{:02}", day))
+    }
+
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.day_dir(year, day).join("input")
+    }
+
+    fn log_path(&self, year: u16, day: u8) -> PathBuf {
+        self.day_dir(year, day).join("submissions.toml")
+    }
+
+    /// The cached input for a day, downloading it first if needed
+    pub fn input(&self, client: &Client, year: u16, day: u8) -> Result<String> {
+        let path = self.input_path(year, day);
+
+        if path.exists() {
+            return input::read_file(&path);
+        }
+
+        let input = client.fetch_input(year, day)?;
+
+        fs::create_dir_all(self.day_dir(year, day))?;
+        fs::write(&path, &input)?;
+
+        Ok(input)
+    }
+
+    pub fn submissions(&self, year: u16, day: u8) -> Result<SubmissionLog> {
+        let path = self.log_path(year, day);
+
+        if !path.exists() {
+            return Ok(SubmissionLog::default());
+        }
+
+        Ok(toml::from_str(&input::read_file(&path)?)?)
+    }
+
+    fn save_submissions(&self, year: u16, day: u8, log: &SubmissionLog) -> Result<()> {
+        fs::create_dir_all(self.day_dir(year, day))?;
+        fs::write(self.log_path(year, day), toml::to_string(log)?)?;
+
+        Ok(())
+    }
+
+    /// Submit an answer unless the log shows it is known to be wrong or we are still rate limited
+    pub fn submit(
+        &self,
+        client: &Client,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict> {
+        let mut log = self.submissions(year, day)?;
+
+        if let Some(reason) = log.rejection(part, answer) {
+            return Err(format!("not submitting day {} part {}: {}", day, part, reason).into());
+        }
+
+        let now = now();
+        if let Some(wait_until) = log.wait_until.filter(|&t| t > now) {
+            return Err(format!("rate limited; wait another {}s", wait_until - now).into());
+        }
+
+        let verdict = client.submit(year, day, part, answer)?;
+
+        log.wait_until = verdict.wait.map(|w| now + w.as_secs());
+
+        if verdict.outcome == Outcome::Correct || verdict.outcome.is_wrong() {
+            log.submissions.push(Submission {
+                part,
+                answer: answer.to_string(),
+                outcome: verdict.outcome,
+            });
+        }
+
+        self.save_submissions(year, day, &log)?;
+
+        Ok(verdict)
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+
+    /// A cache in a fresh temporary directory, removed again when dropped
+    struct TempCache {
+        cache: Cache,
+        root: PathBuf,
+    }
+
+    impl std::ops::Deref for TempCache {
+        type Target = Cache;
+
+        fn deref(&self) -> &Cache {
+            &self.cache
+        }
+    }
+
+    impl Drop for TempCache {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    fn temp_cache(name: &str) -> TempCache {
+        let root = env::temp_dir().join(format!("aoc-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        TempCache {
+            cache: Cache::new(&root),
+            root,
+        }
+    }
+
+    #[test]
+    fn input_cached_test() {
+        let server = MockServer::start(vec![(200, "0,3,6\n")]);
+        let client = Client::new(&server.url, Some("abc123".to_string()));
+        let cache = temp_cache("input");
+
+        assert_eq!(cache.input(&client, 2020, 15).unwrap(), "0,3,6\n");
+        assert_eq!(cache.input(&client, 2020, 15).unwrap(), "0,3,6\n");
+
+        assert_eq!(server.requests().len(), 1);
+        assert!(cache.input_path(2020, 15).ends_with("2020/day15/input"));
+    }
+
+    #[test]
+    fn submit_remembers_wrong_answers_test() {
+        let server = MockServer::start(vec![
+            (
+                200,
+                "<p>That's not the right answer; your answer is too high.</p>",
+            ),
+            (200, "<p>That's the right answer!</p>"),
+        ]);
+        let client = Client::new(&server.url, Some("abc123".to_string()));
+        let cache = temp_cache("submit");
+
+        let verdict = cache.submit(&client, 2020, 1, 1, "1000").unwrap();
+        assert_eq!(verdict.outcome, Outcome::TooHigh);
+
+        assert!(cache.submit(&client, 2020, 1, 1, "1000").is_err());
+        assert!(cache.submit(&client, 2020, 1, 1, "1200").is_err());
+
+        let verdict = cache.submit(&client, 2020, 1, 1, "999").unwrap();
+        assert_eq!(verdict.outcome, Outcome::Correct);
+
+        assert!(cache.submit(&client, 2020, 1, 1, "998").is_err());
+        assert_eq!(server.requests().len(), 2);
+        assert_eq!(cache.submissions(2020, 1).unwrap().submissions.len(), 2);
+    }
+
+    #[test]
+    fn submit_rate_limited_test() {
+        let server = MockServer::start(vec![(
+            200,
+            "<p>You gave an answer too recently.  You have 5m 0s left to wait.</p>",
+        )]);
+        let client = Client::new(&server.url, Some("abc123".to_string()));
+        let cache = temp_cache("rate");
+
+        let verdict = cache.submit(&client, 2020, 2, 1, "42").unwrap();
+        assert_eq!(verdict.outcome, Outcome::RateLimited);
+
+        let err = cache.submit(&client, 2020, 2, 1, "42").unwrap_err();
+        assert!(err.to_string().contains("rate limited"));
+        assert_eq!(server.requests().len(), 1);
+    }
+}
@@ -0,0 +1,268 @@
+use std::env;
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use aoc_core::Result;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!(
+    "aoc-runner/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+/// How the puzzle server judged a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// The answer was not judged because the last submission was too recent
+    RateLimited,
+    /// The part is already solved or not yet unlocked
+    WrongLevel,
+}
+
+impl Outcome {
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect
+        )
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Incorrect => "incorrect",
+            Outcome::RateLimited => "rate limited",
+            Outcome::WrongLevel => "already solved or not yet unlocked",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+/// The judged outcome and how long the server wants us to wait before the next submission
+#[derive(Debug, PartialEq)]
+pub struct Verdict {
+    pub outcome: Outcome,
+    pub wait: Option<Duration>,
+}
+
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: Option<String>) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+
+    /// Configure from `AOC_BASE_URL` and `AOC_SESSION` (the `session` cookie of a logged-in browser)
+    pub fn from_env() -> Client {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let session = env::var("AOC_SESSION").ok().filter(|s| !s.is_empty());
+
+        Client::new(&base_url, session)
+    }
+
+    fn cookie(&self) -> Result<String> {
+        let session = self
+            .session
+            .as_ref()
+            .ok_or("AOC_SESSION is not set; copy the `session` cookie from a logged-in browser")?;
+
+        Ok(format!("session={}", session))
+    }
+
+    pub fn fetch_input(&self, year: u16, day: u8) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie()?)
+            .call()
+            .map_err(|e| format!("could not fetch {}: {}", url, e))?;
+
+        Ok(response.into_string()?)
+    }
+
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Verdict> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie()?)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| format!("could not submit to {}: {}", url, e))?;
+
+        parse_verdict(&response.into_string()?)
+    }
+}
+
+/// Interpret the HTML page returned for a submission
+pub fn parse_verdict(body: &str) -> Result<Verdict> {
+    if body.contains("That's the right answer") {
+        Ok(Verdict {
+            outcome: Outcome::Correct,
+            wait: None,
+        })
+    } else if body.contains("That's not the right answer") {
+        let outcome = if body.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if body.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        };
+
+        Ok(Verdict {
+            outcome,
+            wait: parse_penalty(body),
+        })
+    } else if body.contains("You gave an answer too recently") {
+        Ok(Verdict {
+            outcome: Outcome::RateLimited,
+            wait: parse_time_left(body),
+        })
+    } else if body.contains("You don't seem to be solving the right level") {
+        Ok(Verdict {
+            outcome: Outcome::WrongLevel,
+            wait: None,
+        })
+    } else {
+        Err("unrecognised response to submission".into())
+    }
+}
+
+/// "please wait one minute before trying again" / "please wait 5 minutes before trying again"
+fn parse_penalty(body: &str) -> Option<Duration> {
+    let body = body.to_lowercase();
+    let rest = &body[body.find("please wait ")? + "please wait ".len()..];
+    let (count, rest) = rest.split_once(' ')?;
+
+    let count = match count {
+        "one" => 1,
+        n => n.parse().ok()?,
+    };
+
+    if rest.starts_with("minute") {
+        Some(Duration::from_secs(60 * count))
+    } else {
+        None
+    }
+}
+
+/// "You have 1m 23s left to wait." / "You have 45s left to wait."
+fn parse_time_left(body: &str) -> Option<Duration> {
+    let start = body.find("You have ")? + "You have ".len();
+    let end = start + body[start..].find(" left to wait")?;
+
+    let mut secs = 0;
+    for token in body[start..end].split_whitespace() {
+        if let Some(m) = token.strip_suffix('m') {
+            secs += 60 * m.parse::<u64>().ok()?;
+        } else if let Some(s) = token.strip_suffix('s') {
+            secs += s.parse::<u64>().ok()?;
+        } else {
+            return None;
+        }
+    }
+
+    Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockServer;
+
+    #[test]
+    fn parse_verdict_test() {
+        let v = parse_verdict("<p>That's the right answer!  You are one gold star closer</p>");
+        assert_eq!(v.unwrap().outcome, Outcome::Correct);
+
+        let v = parse_verdict(
+            "<p>That's not the right answer; your answer is too high.  \
+             Please wait one minute before trying again.</p>",
+        )
+        .unwrap();
+        assert_eq!(v.outcome, Outcome::TooHigh);
+        assert_eq!(v.wait, Some(Duration::from_secs(60)));
+
+        let v = parse_verdict(
+            "<p>That's not the right answer.  please wait 5 minutes before trying again.</p>",
+        )
+        .unwrap();
+        assert_eq!(v.outcome, Outcome::Incorrect);
+        assert_eq!(v.wait, Some(Duration::from_secs(300)));
+
+        let v =
+            parse_verdict("<p>You gave an answer too recently.  You have 1m 23s left to wait.</p>")
+                .unwrap();
+        assert_eq!(v.outcome, Outcome::RateLimited);
+        assert_eq!(v.wait, Some(Duration::from_secs(83)));
+
+        assert!(parse_verdict("<html>Puzzle inputs differ by user.</html>").is_err());
+    }
+
+    #[test]
+    fn fetch_input_test() {
+        let server = MockServer::start(vec![(200, "1721\n979\n366\n")]);
+        let client = Client::new(&server.url, Some("abc123".to_string()));
+
+        assert_eq!(client.fetch_input(2020, 1).unwrap(), "1721\n979\n366\n");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].url, "/2020/day/1/input");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+    }
+
+    #[test]
+    fn fetch_input_error_test() {
+        let server =
+            MockServer::start(vec![(404, "Please don't repeatedly request this endpoint")]);
+        let client = Client::new(&server.url, Some("abc123".to_string()));
+
+        assert!(client.fetch_input(2020, 26).is_err());
+        assert!(Client::new(&server.url, None).fetch_input(2020, 1).is_err());
+    }
+
+    #[test]
+    fn submit_test() {
+        let server = MockServer::start(vec![(200, "<p>That's the right answer!</p>")]);
+        let client = Client::new(&server.url, Some("abc123".to_string()));
+
+        let verdict = client.submit(2020, 21, 2, "rcqb,cltx").unwrap();
+        assert_eq!(verdict.outcome, Outcome::Correct);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].url, "/2020/day/21/answer");
+        assert_eq!(requests[0].body, "level=2&answer=rcqb%2Ccltx");
+    }
+}
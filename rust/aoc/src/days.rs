@@ -0,0 +1,177 @@
+use std::path::PathBuf;
+
+use aoc_core::{Report, Result};
+
+pub use crate::year::DEFAULT_YEAR;
+
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub run: fn(&str) -> Result<Report>,
+}
+
+impl Day {
+    /// Location of the committed puzzle input, e.g. `rust/aoc14/input`
+    pub fn input_path(&self) -> PathBuf {
+        workspace_dir()
+            .join(crate_dir(self.year, self.day))
+            .join("input")
+    }
+}
+
+/// The `rust/` directory holding the cargo workspace
+pub fn workspace_dir() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), ".."].iter().collect()
+}
+
+/// Package name of a day's crate: `aoc14` for the default year, `aoc2021_14` otherwise
+pub fn crate_name(year: u16, day: u8) -> String {
+    if year == DEFAULT_YEAR {
+        format!("aoc{:02}", day)
+    } else {
+        format!("aoc{}_{:02}", year, day)
+    }
+}
+
+/// A day's crate directory relative to `rust/`: `aoc14` for the default year, `2021/aoc14` otherwise
+pub fn crate_dir(year: u16, day: u8) -> PathBuf {
+    let name = format!("aoc{:02}", day);
+
+    if year == DEFAULT_YEAR {
+        PathBuf::from(name)
+    } else {
+        [year.to_string(), name].iter().collect()
+    }
+}
+
+pub static DAYS: &[Day] = &[
+    Day {
+        year: 2020,
+        day: 1,
+        run: aoc_core::run::<aoc01::Day01>,
+    },
+    Day {
+        year: 2020,
+        day: 2,
+        run: aoc_core::run::<aoc02::Day02>,
+    },
+    Day {
+        year: 2020,
+        day: 3,
+        run: aoc_core::run::<aoc03::Day03>,
+    },
+    Day {
+        year: 2020,
+        day: 4,
+        run: aoc_core::run::<aoc04::Day04>,
+    },
+    Day {
+        year: 2020,
+        day: 5,
+        run: aoc_core::run::<aoc05::Day05>,
+    },
+    Day {
+        year: 2020,
+        day: 6,
+        run: aoc_core::run::<aoc06::Day06>,
+    },
+    Day {
+        year: 2020,
+        day: 7,
+        run: aoc_core::run::<aoc07::Day07>,
+    },
+    Day {
+        year: 2020,
+        day: 8,
+        run: aoc_core::run::<aoc08::Day08>,
+    },
+    Day {
+        year: 2020,
+        day: 9,
+        run: aoc_core::run::<aoc09::Day09>,
+    },
+    Day {
+        year: 2020,
+        day: 10,
+        run: aoc_core::run::<aoc10::Day10>,
+    },
+    Day {
+        year: 2020,
+        day: 11,
+        run: aoc_core::run::<aoc11::Day11>,
+    },
+    Day {
+        year: 2020,
+        day: 12,
+        run: aoc_core::run::<aoc12::Day12>,
+    },
+    Day {
+        year: 2020,
+        day: 13,
+        run: aoc_core::run::<aoc13::Day13>,
+    },
+    Day {
+        year: 2020,
+        day: 14,
+        run: aoc_core::run::<aoc14::Day14>,
+    },
+    Day {
+        year: 2020,
+        day: 15,
+        run: aoc_core::run::<aoc15::Day15>,
+    },
+    Day {
+        year: 2020,
+        day: 16,
+        run: aoc_core::run::<aoc16::Day16>,
+    },
+    Day {
+        year: 2020,
+        day: 17,
+        run: aoc_core::run::<aoc17::Day17>,
+    },
+    Day {
+        year: 2020,
+        day: 18,
+        run: aoc_core::run::<aoc18::Day18>,
+    },
+    Day {
+        year: 2020,
+        day: 19,
+        run: aoc_core::run::<aoc19::Day19>,
+    },
+    Day {
+        year: 2020,
+        day: 20,
+        run: aoc_core::run::<aoc20::Day20>,
+    },
+    Day {
+        year: 2020,
+        day: 21,
+        run: aoc_core::run::<aoc21::Day21>,
+    },
+    Day {
+        year: 2020,
+        day: 22,
+        run: aoc_core::run::<aoc22::Day22>,
+    },
+    Day {
+        year: 2020,
+        day: 23,
+        run: aoc_core::run::<aoc23::Day23>,
+    },
+    Day {
+        year: 2020,
+        day: 24,
+        run: aoc_core::run::<aoc24::Day24>,
+    },
+    Day {
+        year: 2020,
+        day: 25,
+        run: aoc_core::run::<aoc25::Day25>,
+    },
+];
+
+pub fn get(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
@@ -0,0 +1,393 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_core::{input, Answer, Result};
+
+use crate::cache::Cache;
+use crate::client::{Client, Outcome};
+use crate::days::DEFAULT_YEAR;
+
+mod answers;
+mod cache;
+mod client;
+mod days;
+#[cfg(test)]
+mod mock;
+mod scaffold;
+mod verify;
+mod year;
+
+const USAGE: &str = "Usage: aoc run <days> [--year <year>] [--input <file>]
+       aoc verify [<days>] [--year <year>]
+       aoc fetch <days> [--year <year>] [--output <file>]
+       aoc submit <day> <part> <answer> [--year <year>]
+       aoc new <year> <day> [--fetch]
+
+  <days> is a single day (14), a range (1..=25 or 1..25) or `all`
+  <year> defaults to 2020";
+
+#[derive(Debug)]
+struct Timing {
+    day: u8,
+    parse: Option<Duration>,
+    part1: Option<Duration>,
+    part2: Option<Duration>,
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if let Err(e) = run_command(&args) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run_command(args: &[String]) -> Result<()> {
+    match args.first().map(|s| s.as_str()) {
+        Some("run") => {
+            let mut spec = None;
+            let mut year = DEFAULT_YEAR;
+            let mut input = None;
+
+            let mut it = args[1..].iter();
+            while let Some(arg) = it.next() {
+                match arg.as_str() {
+                    "--year" => year = parse_year(it.next())?,
+                    "--input" => {
+                        let path = it.next().ok_or("--input requires a file")?;
+                        input = Some(PathBuf::from(path));
+                    }
+                    _ if spec.is_none() => spec = Some(arg.as_str()),
+                    _ => return Err(format!("unexpected argument `{}`\n\n{}", arg, USAGE).into()),
+                }
+            }
+
+            let days = parse_days(year, spec.ok_or(USAGE)?)?;
+
+            if input.is_some() && days.len() != 1 {
+                return Err("--input can only be used when running a single day".into());
+            }
+
+            run_days(year, &days, input)
+        }
+        Some("verify") => {
+            let mut spec = None;
+            let mut year = DEFAULT_YEAR;
+
+            let mut it = args[1..].iter();
+            while let Some(arg) = it.next() {
+                match arg.as_str() {
+                    "--year" => year = parse_year(it.next())?,
+                    _ if spec.is_none() => spec = Some(arg.as_str()),
+                    _ => return Err(format!("unexpected argument `{}`\n\n{}", arg, USAGE).into()),
+                }
+            }
+
+            let days = parse_days(year, spec.unwrap_or("all"))?;
+
+            verify::verify(year, &days)
+        }
+        Some("fetch") => {
+            let mut days = None;
+            let mut year = DEFAULT_YEAR;
+            let mut output = None;
+
+            let mut it = args[1..].iter();
+            while let Some(arg) = it.next() {
+                match arg.as_str() {
+                    "--year" => year = parse_year(it.next())?,
+                    "--output" => {
+                        let path = it.next().ok_or("--output requires a file")?;
+                        output = Some(PathBuf::from(path));
+                    }
+                    _ if days.is_none() => days = Some(parse_day_range(arg)?),
+                    _ => return Err(format!("unexpected argument `{}`\n\n{}", arg, USAGE).into()),
+                }
+            }
+
+            let days = days.ok_or(USAGE)?;
+
+            if output.is_some() && days.len() != 1 {
+                return Err("--output can only be used when fetching a single day".into());
+            }
+
+            fetch_days(year, &days, output)
+        }
+        Some("submit") => {
+            let mut positional = Vec::new();
+            let mut year = DEFAULT_YEAR;
+
+            let mut it = args[1..].iter();
+            while let Some(arg) = it.next() {
+                match arg.as_str() {
+                    "--year" => year = parse_year(it.next())?,
+                    _ => positional.push(arg.as_str()),
+                }
+            }
+
+            match positional[..] {
+                [day, part, answer] => {
+                    submit(year, parse_day(day, "submit")?, part.parse()?, answer)
+                }
+                _ => Err(USAGE.into()),
+            }
+        }
+        Some("new") => {
+            let mut positional = Vec::new();
+            let mut fetch = false;
+
+            for arg in &args[1..] {
+                match arg.as_str() {
+                    "--fetch" => fetch = true,
+                    _ => positional.push(arg.as_str()),
+                }
+            }
+
+            match positional[..] {
+                [year, day] => {
+                    let year = parse_year(Some(year))?;
+                    let day = parse_day(day, "new")?;
+
+                    scaffold::new_day(&days::workspace_dir(), year, day, fetch)
+                }
+                _ => Err(USAGE.into()),
+            }
+        }
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn parse_year<S: AsRef<str>>(arg: Option<S>) -> Result<u16> {
+    let arg = arg.ok_or("--year requires a year")?;
+    let year = arg.as_ref().parse()?;
+
+    if year < 2015 {
+        return Err(format!("there is no Advent of Code {}", year).into());
+    }
+
+    Ok(year)
+}
+
+/// Days named by `spec`, whether or not they have a solution yet
+fn parse_day_range(spec: &str) -> Result<Vec<u8>> {
+    let days: Vec<u8> = if spec == "all" {
+        (1..=25).collect()
+    } else if let Some((start, end)) = spec.split_once("..=") {
+        (start.parse()?..=end.parse()?).collect()
+    } else if let Some((start, end)) = spec.split_once("..") {
+        (start.parse()?..end.parse()?).collect()
+    } else {
+        vec![spec.parse()?]
+    };
+
+    if days.is_empty() {
+        return Err(format!("no days in `{}`", spec).into());
+    }
+
+    if let Some(d) = days.iter().find(|d| !(1..=25).contains(*d)) {
+        return Err(format!("there is no day {}", d).into());
+    }
+
+    Ok(days)
+}
+
+/// A single day, for the commands that only take one
+fn parse_day(spec: &str, command: &str) -> Result<u8> {
+    match parse_day_range(spec)?[..] {
+        [day] => Ok(day),
+        _ => Err(format!("`aoc {}` takes a single day", command).into()),
+    }
+}
+
+fn parse_days(year: u16, spec: &str) -> Result<Vec<u8>> {
+    let mut days = parse_day_range(spec)?;
+
+    // `all` means every registered day, so a year in progress can still be run as a whole
+    if spec == "all" {
+        days.retain(|d| days::get(year, *d).is_some());
+    }
+
+    if days.is_empty() {
+        return Err(format!("no solutions for {}", year).into());
+    }
+
+    if let Some(d) = days.iter().find(|d| days::get(year, **d).is_none()) {
+        return Err(format!("no solution for {} day {}", year, d).into());
+    }
+
+    Ok(days)
+}
+
+fn run_days(year: u16, days: &[u8], input: Option<PathBuf>) -> Result<()> {
+    let mut timings = Vec::with_capacity(days.len());
+
+    for &d in days {
+        let day = days::get(year, d).ok_or(format!("no solution for {} day {}", year, d))?;
+        let path = input.clone().unwrap_or_else(|| day.input_path());
+
+        println!("day {}", d);
+
+        let mut timing = Timing {
+            day: d,
+            parse: None,
+            part1: None,
+            part2: None,
+        };
+
+        let report = input::read_file(&path).and_then(|input| (day.run)(&input));
+
+        match report {
+            Ok(report) => {
+                timing.parse = Some(report.parse);
+
+                timing.part1 = report_part(d, 1, &report.part1);
+                timing.part2 = report_part(d, 2, &report.part2);
+            }
+            Err(e) => {
+                eprintln!("day {} failed: {}", d, e);
+            }
+        }
+
+        timings.push(timing);
+    }
+
+    print_summary(&timings);
+
+    let failed = timings
+        .iter()
+        .flat_map(|t| vec![t.part1, t.part2])
+        .filter(|p| p.is_none())
+        .count();
+
+    if failed > 0 {
+        return Err(format!("{} part(s) failed", failed).into());
+    }
+
+    Ok(())
+}
+
+fn fetch_days(year: u16, days: &[u8], output: Option<PathBuf>) -> Result<()> {
+    let client = Client::from_env();
+    let cache = Cache::from_env();
+
+    for &d in days {
+        let input = cache.input(&client, year, d)?;
+
+        match &output {
+            Some(path) => {
+                fs::write(path, &input)?;
+                println!("day {}: {}", d, path.display());
+            }
+            None => println!("day {}: {}", d, cache.input_path(year, d).display()),
+        }
+    }
+
+    Ok(())
+}
+
+fn submit(year: u16, day: u8, part: u8, answer: &str) -> Result<()> {
+    if part != 1 && part != 2 {
+        return Err(format!("there is no part {}", part).into());
+    }
+
+    let verdict = Cache::from_env().submit(&Client::from_env(), year, day, part, answer)?;
+
+    match verdict.wait {
+        Some(wait) => println!(
+            "day {} part {}: {} (wait {}s before submitting again)",
+            day,
+            part,
+            verdict.outcome,
+            wait.as_secs()
+        ),
+        None => println!("day {} part {}: {}", day, part, verdict.outcome),
+    }
+
+    if verdict.outcome != Outcome::Correct {
+        return Err(format!("`{}` was not accepted", answer).into());
+    }
+
+    Ok(())
+}
+
+fn report_part(day: u8, part: u8, answer: &Result<Answer>) -> Option<Duration> {
+    match answer {
+        Ok(answer) => {
+            println!("part {} solution: {}", part, answer.value);
+            Some(answer.elapsed)
+        }
+        Err(e) => {
+            eprintln!("day {} part {} failed: {}", day, part, e);
+            None
+        }
+    }
+}
+
+fn format_elapsed(d: Option<Duration>) -> String {
+    match d {
+        Some(d) => format!("{:?}", d),
+        None => "-".to_string(),
+    }
+}
+
+fn print_summary(timings: &[Timing]) {
+    println!();
+    println!(
+        "{:>5}  {:>14}  {:>14}  {:>14}",
+        "day", "parse", "part 1", "part 2"
+    );
+
+    for t in timings {
+        println!(
+            "{:>5}  {:>14}  {:>14}  {:>14}",
+            t.day,
+            format_elapsed(t.parse),
+            format_elapsed(t.part1),
+            format_elapsed(t.part2)
+        );
+    }
+
+    let total: Duration = timings
+        .iter()
+        .flat_map(|t| t.parse.iter().chain(t.part1.iter()).chain(t.part2.iter()))
+        .sum();
+
+    println!("{:>5}  {:>14}", "total", format!("{:?}", total));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_days_test() {
+        assert_eq!(parse_days(2020, "14").unwrap(), vec![14]);
+        assert_eq!(parse_days(2020, "1..=3").unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_days(2020, "1..3").unwrap(), vec![1, 2]);
+        assert_eq!(parse_days(2020, "all").unwrap().len(), 25);
+
+        assert!(parse_days(2020, "26").is_err());
+        assert!(parse_days(2020, "0..=2").is_err());
+        assert!(parse_days(2020, "3..1").is_err());
+        assert!(parse_days(2020, "x").is_err());
+        assert!(parse_days(1999, "all").is_err());
+
+        assert_eq!(parse_day_range("24..=25").unwrap(), vec![24, 25]);
+        assert!(parse_day_range("25..=26").is_err());
+
+        assert_eq!(parse_day("7", "submit").unwrap(), 7);
+        assert!(parse_day("1..=2", "submit").is_err());
+
+        // Rejected before anything is sent to the puzzle site
+        let args: Vec<String> = ["submit", "40", "1", "123"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            run_command(&args).unwrap_err().to_string(),
+            "there is no day 40"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! A local HTTP server that answers with canned responses and records every request it saw
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use tiny_http::{Response, Server};
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub url: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    /// Serve `responses` as `(status, body)` in order, one per incoming request
+    pub fn start(responses: Vec<(u16, &str)>) -> MockServer {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let responses: Vec<(u16, String)> = responses
+            .into_iter()
+            .map(|(status, body)| (status, body.to_string()))
+            .collect();
+        let seen = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let mut request = match server.recv() {
+                    Ok(request) => request,
+                    Err(_) => return,
+                };
+
+                let mut content = String::new();
+                request.as_reader().read_to_string(&mut content).unwrap();
+
+                seen.lock().unwrap().push(Request {
+                    method: request.method().to_string(),
+                    url: request.url().to_string(),
+                    cookie: request
+                        .headers()
+                        .iter()
+                        .find(|h| h.field.equiv("Cookie"))
+                        .map(|h| h.value.to_string()),
+                    body: content,
+                });
+
+                let response = Response::from_string(body).with_status_code(status);
+                request.respond(response).unwrap();
+            }
+        });
+
+        MockServer { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,245 @@
+use std::fs;
+use std::path::Path;
+
+use aoc_core::{input, Result};
+
+use crate::cache::Cache;
+use crate::client::Client;
+use crate::days::{self, DEFAULT_YEAR};
+
+const CARGO_TEMPLATE: &str = include_str!("../../day_template/Cargo.toml.in");
+const LIB_TEMPLATE: &str = include_str!("../../day_template/lib.rs");
+const MAIN_TEMPLATE: &str = include_str!("../../day_template/main.rs");
+const EXAMPLE_TEMPLATE: &str = include_str!("../../day_template/example.toml");
+
+/// Add `dir` to the workspace members, just before the runner
+fn add_member(manifest: &str, dir: &str) -> Result<String> {
+    let anchor = "    \"aoc\",\n";
+    let at = manifest
+        .find(anchor)
+        .ok_or("could not find the runner in the workspace members")?;
+
+    Ok(format!(
+        "{}    \"{}\",\n{}",
+        &manifest[..at],
+        dir,
+        &manifest[at..]
+    ))
+}
+
+/// Add a path dependency on a day crate after the runner's last one
+fn add_dependency(manifest: &str, name: &str, path: &str) -> Result<String> {
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    let mut section = "";
+    let mut last = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with('[') {
+            section = line;
+        } else if section == "[dependencies]" && line.starts_with("aoc") && line.contains("path") {
+            last = Some(i);
+        }
+    }
+
+    let last = last.ok_or("could not find the day crates in the runner's dependencies")?;
+    let dependency = format!("{} = {{ path = \"{}\" }}", name, path);
+    lines.insert(last + 1, &dependency);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Insert `entry` before the end of the list that starts at `start`
+fn insert_before_close(source: &str, start: &str, close: &str, entry: &str) -> Result<String> {
+    let begin = source
+        .find(start)
+        .ok_or(format!("could not find `{}`", start))?;
+    let at = begin
+        + source[begin..]
+            .find(close)
+            .ok_or(format!("could not find the end of `{}`", start))?
+        + 1;
+
+    Ok(format!("{}{}{}", &source[..at], entry, &source[at..]))
+}
+
+/// Register a day in the runner's dispatch table
+fn add_day(source: &str, year: u16, day: u8, name: &str) -> Result<String> {
+    let entry = format!(
+        "    Day {{\n        year: {},\n        day: {},\n        run: aoc_core::run::<{}::Day{:02}>,\n    }},\n",
+        year, day, name, day
+    );
+
+    insert_before_close(source, "pub static DAYS", "\n];", &entry)
+}
+
+/// Register a day with the criterion benchmarks
+fn add_bench(source: &str, year: u16, day: u8, name: &str) -> Result<String> {
+    let entry = format!("    ({}, {}) => {}::Day{:02},\n", year, day, name, day);
+
+    insert_before_close(source, "bench_days! {", "\n}", &entry)
+}
+
+/// Create the crate for a new day and register it with the workspace, runner and benchmarks
+pub fn new_day(root: &Path, year: u16, day: u8, fetch: bool) -> Result<()> {
+    let name = days::crate_name(year, day);
+    let dir = days::crate_dir(year, day);
+    let dir_name = dir.to_string_lossy().replace('\\', "/");
+    let crate_dir = root.join(&dir);
+
+    if crate_dir.exists() {
+        return Err(format!("{} already exists", crate_dir.display()).into());
+    }
+
+    // Prepare every edit up front so a failure leaves the tree untouched
+    let workspace_manifest = root.join("Cargo.toml");
+    let runner_manifest = root.join("aoc").join("Cargo.toml");
+    let dispatch = root.join("aoc").join("src").join("days.rs");
+    let benches = root.join("aoc").join("benches").join("days.rs");
+
+    let edits = vec![
+        (
+            add_member(&input::read_file(&workspace_manifest)?, &dir_name)?,
+            workspace_manifest,
+        ),
+        (
+            add_dependency(
+                &input::read_file(&runner_manifest)?,
+                &name,
+                &format!("../{}", dir_name),
+            )?,
+            runner_manifest,
+        ),
+        (
+            add_day(&input::read_file(&dispatch)?, year, day, &name)?,
+            dispatch,
+        ),
+        (
+            add_bench(&input::read_file(&benches)?, year, day, &name)?,
+            benches,
+        ),
+    ];
+
+    let puzzle_input = if fetch {
+        Cache::from_env().input(&Client::from_env(), year, day)?
+    } else {
+        String::new()
+    };
+
+    let core_path = if year == DEFAULT_YEAR {
+        "../aoc-core"
+    } else {
+        "../../aoc-core"
+    };
+    let fill = |template: &str| {
+        template
+            .replace("CRATE", &name)
+            .replace("CORE_PATH", core_path)
+            .replace("XX", &format!("{:02}", day))
+    };
+
+    fs::create_dir_all(crate_dir.join("src"))?;
+    fs::create_dir_all(crate_dir.join("examples"))?;
+
+    fs::write(crate_dir.join("Cargo.toml"), fill(CARGO_TEMPLATE))?;
+    fs::write(crate_dir.join("src").join("lib.rs"), fill(LIB_TEMPLATE))?;
+    fs::write(crate_dir.join("src").join("main.rs"), fill(MAIN_TEMPLATE))?;
+    fs::write(crate_dir.join("input"), puzzle_input)?;
+    fs::write(crate_dir.join("examples").join("example.txt"), "")?;
+    fs::write(
+        crate_dir.join("examples").join("example.toml"),
+        EXAMPLE_TEMPLATE,
+    )?;
+
+    for (contents, path) in edits {
+        fs::write(path, contents)?;
+    }
+
+    println!("created {} in {}", name, crate_dir.display());
+
+    if !fetch {
+        println!(
+            "add the puzzle input to {}",
+            crate_dir.join("input").display()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_test() {
+        let manifest =
+            "[workspace]\nmembers = [\n    \"aoc01\",\n    \"aoc\",\n    \"aoc-core\",\n]\n";
+        assert_eq!(
+            add_member(manifest, "2021/aoc01").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc01\",\n    \"2021/aoc01\",\n    \"aoc\",\n    \"aoc-core\",\n]\n"
+        );
+
+        let manifest = "[package]\nname = \"aoc\"\n\n[dependencies]\naoc-core = { path = \"../aoc-core\" }\naoc01 = { path = \"../aoc01\" }\nserde = \"1\"\n\n[dev-dependencies]\naoc02 = { path = \"../aoc02\" }\n";
+        assert_eq!(
+            add_dependency(manifest, "aoc2021_01", "../2021/aoc01").unwrap(),
+            "[package]\nname = \"aoc\"\n\n[dependencies]\naoc-core = { path = \"../aoc-core\" }\naoc01 = { path = \"../aoc01\" }\naoc2021_01 = { path = \"../2021/aoc01\" }\nserde = \"1\"\n\n[dev-dependencies]\naoc02 = { path = \"../aoc02\" }\n"
+        );
+
+        let source = "pub static DAYS: &[Day] = &[\n    Day {\n        year: 2020,\n        day: 1,\n        run: aoc_core::run::<aoc01::Day01>,\n    },\n];\n";
+        assert!(add_day(source, 2021, 1, "aoc2021_01")
+            .unwrap()
+            .ends_with("    Day {\n        year: 2021,\n        day: 1,\n        run: aoc_core::run::<aoc2021_01::Day01>,\n    },\n];\n"));
+
+        let source = "bench_days! {\n    (2020, 1) => aoc01::Day01,\n}\n";
+        assert_eq!(
+            add_bench(source, 2021, 1, "aoc2021_01").unwrap(),
+            "bench_days! {\n    (2020, 1) => aoc01::Day01,\n    (2021, 1) => aoc2021_01::Day01,\n}\n"
+        );
+
+        assert!(add_day("", 2021, 1, "aoc2021_01").is_err());
+    }
+
+    #[test]
+    fn new_day_test() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        let workspace = days::workspace_dir();
+        for file in &[
+            "Cargo.toml",
+            "aoc/Cargo.toml",
+            "aoc/src/days.rs",
+            "aoc/benches/days.rs",
+        ] {
+            let to = root.join(file);
+            fs::create_dir_all(to.parent().unwrap()).unwrap();
+            fs::copy(workspace.join(file), to).unwrap();
+        }
+
+        new_day(&root, 2021, 3, false).unwrap();
+
+        let lib = input::read_file(root.join("2021/aoc03/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day03;"));
+        assert!(lib.contains("mod tests"));
+        assert!(lib.contains("const PART1: Option<usize> = None;"));
+
+        let main = input::read_file(root.join("2021/aoc03/src/main.rs")).unwrap();
+        assert!(main.contains("aoc2021_03::Day03"));
+
+        let manifest = input::read_file(root.join("2021/aoc03/Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"aoc2021_03\""));
+        assert!(manifest.contains("path = \"../../aoc-core\""));
+
+        assert!(root.join("2021/aoc03/examples/example.txt").exists());
+        assert!(input::read_file(root.join("Cargo.toml"))
+            .unwrap()
+            .contains("\"2021/aoc03\""));
+        assert!(input::read_file(root.join("aoc/src/days.rs"))
+            .unwrap()
+            .contains("aoc2021_03::Day03"));
+
+        assert!(new_day(&root, 2021, 3, false).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,131 @@
+use aoc_core::{input, Answer, Result};
+
+use crate::answers::{self, Answers};
+use crate::days;
+
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String, actual: String },
+    Error(String),
+    Unrecorded,
+}
+
+#[derive(Debug)]
+pub struct DayCheck {
+    pub day: u8,
+    pub part1: Check,
+    pub part2: Check,
+}
+
+impl DayCheck {
+    pub fn passed(&self) -> bool {
+        [&self.part1, &self.part2]
+            .iter()
+            .all(|c| matches!(c, Check::Pass | Check::Unrecorded))
+    }
+}
+
+fn check(expected: Option<&toml::Value>, actual: &Result<Answer>) -> Check {
+    match (expected, actual) {
+        (_, Err(e)) => Check::Error(e.to_string()),
+        (None, Ok(_)) => Check::Unrecorded,
+        (Some(expected), Ok(answer)) => {
+            let expected = answers::expected(expected);
+            if expected == answer.value {
+                Check::Pass
+            } else {
+                Check::Fail {
+                    expected,
+                    actual: answer.value.clone(),
+                }
+            }
+        }
+    }
+}
+
+/// Rerun each day on its committed input and compare against `answers`
+pub fn verify_days(year: u16, days: &[u8], answers: &Answers) -> Result<Vec<DayCheck>> {
+    let mut checks = Vec::with_capacity(days.len());
+
+    for &d in days {
+        let day = days::get(year, d).ok_or(format!("no solution for {} day {}", year, d))?;
+        let recorded = answers.get(year, d);
+
+        let report = input::read_file(day.input_path()).and_then(|input| (day.run)(&input));
+
+        let check = match report {
+            Ok(report) => DayCheck {
+                day: d,
+                part1: check(recorded.and_then(|a| a.part1.as_ref()), &report.part1),
+                part2: check(recorded.and_then(|a| a.part2.as_ref()), &report.part2),
+            },
+            Err(e) => DayCheck {
+                day: d,
+                part1: Check::Error(e.to_string()),
+                part2: Check::Error(e.to_string()),
+            },
+        };
+
+        checks.push(check);
+    }
+
+    Ok(checks)
+}
+
+fn describe(c: &Check) -> String {
+    match c {
+        Check::Pass => "ok".to_string(),
+        Check::Fail { expected, actual } => format!("expected {}, got {}", expected, actual),
+        Check::Error(e) => format!("error: {}", e),
+        Check::Unrecorded => "no recorded answer".to_string(),
+    }
+}
+
+pub fn verify(year: u16, days: &[u8]) -> Result<()> {
+    let answers = Answers::load(Answers::default_path())?;
+    let checks = verify_days(year, days, &answers)?;
+
+    for c in checks.iter() {
+        println!(
+            "day {:>2}  part 1: {:<24}  part 2: {}",
+            c.day,
+            describe(&c.part1),
+            describe(&c.part2)
+        );
+    }
+
+    let failed = checks.iter().filter(|c| !c.passed()).count();
+    if failed > 0 {
+        return Err(format!("{} day(s) do not match the recorded answers", failed).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_answers_test() {
+        let answers = Answers::load(Answers::default_path()).unwrap();
+
+        let mut years: Vec<u16> = days::DAYS.iter().map(|d| d.year).collect();
+        years.sort_unstable();
+        years.dedup();
+
+        for year in years {
+            let days: Vec<u8> = days::DAYS
+                .iter()
+                .filter(|d| d.year == year)
+                .map(|d| d.day)
+                .collect();
+
+            // A freshly scaffolded day has nothing recorded yet and is skipped
+            for c in verify_days(year, &days, &answers).unwrap() {
+                assert!(c.passed(), "{} day {}: {:?}", year, c.day, c);
+            }
+        }
+    }
+}
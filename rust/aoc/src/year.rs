@@ -0,0 +1,4 @@
+/// The year used when `--year` is not given. Its crates live directly under `rust/` as `aocNN`.
+///
+/// Shared with `build.rs` and the benchmarks, which include this file as a module.
+pub const DEFAULT_YEAR: u16 = 2020;
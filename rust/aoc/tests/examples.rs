@@ -0,0 +1,56 @@
+//! Runs every day's solution on the puzzle examples kept in `aocNN/examples/`.
+//!
+//! `NAME.txt` holds the example input and the sidecar `NAME.toml` records the expected `part1`
+//! and/or `part2` answer. Only the recorded parts are run, since many examples are only valid
+//! for one part. The tests themselves are generated by `build.rs`, so adding a case only means
+//! adding the two files; a sidecar that records no answer yet, like a freshly scaffolded stub,
+//! makes an ignored test rather than one that passes without checking anything.
+
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use aoc_core::{input, Solution};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Expected {
+    part1: Option<toml::Value>,
+    part2: Option<toml::Value>,
+}
+
+fn render(v: &toml::Value) -> String {
+    match v {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Check example `name` of the day crate in `crate_dir`, relative to `rust/`
+fn check<S: Solution>(crate_dir: &str, name: &str) {
+    let dir: PathBuf = [env!("CARGO_MANIFEST_DIR"), "..", crate_dir, "examples"]
+        .iter()
+        .collect();
+
+    let raw = input::read_file(dir.join(format!("{}.txt", name))).unwrap();
+    let sidecar = input::read_file(dir.join(format!("{}.toml", name))).unwrap();
+    let expected: Expected = toml::from_str(&sidecar).unwrap();
+
+    assert!(
+        expected.part1.is_some() || expected.part2.is_some(),
+        "{}.toml records no answers",
+        name
+    );
+
+    let parsed = S::parse(&raw).unwrap();
+
+    if let Some(v) = &expected.part1 {
+        assert_eq!(S::part1(&parsed).unwrap().to_string(), render(v), "part 1");
+    }
+
+    if let Some(v) = &expected.part2 {
+        assert_eq!(S::part2(&parsed).unwrap().to_string(), render(v), "part 2");
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
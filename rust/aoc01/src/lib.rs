@@ -0,0 +1,256 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use aoc_core::{input, Result, Solution};
+
+/// The sum the expense report entries are audited against
+pub const TARGET: i64 = 2020;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input<'a> = Vec<i64>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>> {
+        input::parse_lines(input)
+    }
+
+    fn part1(values: &Vec<i64>) -> Result<i64> {
+        let found = first_k_sum(values, 2, TARGET).ok_or("no two entries sum to 2020")?;
+
+        product(values, &found)
+    }
+
+    fn part2(values: &Vec<i64>) -> Result<i64> {
+        let found = first_k_sum(values, 3, TARGET).ok_or("no three entries sum to 2020")?;
+
+        product(values, &found)
+    }
+}
+
+/// The product of the entries at `indices`
+pub fn product(values: &[i64], indices: &[usize]) -> Result<i64> {
+    indices
+        .iter()
+        .try_fold(1i64, |p, &i| p.checked_mul(values[i]))
+        .ok_or_else(|| "the product of the entries is too large".into())
+}
+
+/// The sum of `values`, or `None` if it does not fit an `i64`
+fn checked_sum(values: &[i64]) -> Option<i64> {
+    values.iter().try_fold(0i64, |s, &v| s.checked_add(v))
+}
+
+/// Every set of `k` distinct entries summing to `target`, as ascending
+/// indices into `values`
+pub fn k_sum(values: &[i64], k: usize, target: i64) -> Vec<Vec<usize>> {
+    let mut found = vec![];
+
+    search(values, k, target, &mut |t| {
+        found.push(t.to_vec());
+        true
+    });
+
+    found
+}
+
+/// The first set of `k` distinct entries found summing to `target`
+pub fn first_k_sum(values: &[i64], k: usize, target: i64) -> Option<Vec<usize>> {
+    let mut found = None;
+
+    search(values, k, target, &mut |t| {
+        found = Some(t.to_vec());
+        false
+    });
+
+    found
+}
+
+/// Calls `visit` with each matching index tuple until it returns `false`.
+/// Pairs are found with a hash map of the entries seen so far; larger groups
+/// sort the entries and fix all but the last two before a two-pointer scan.
+/// Groups that would need a partial sum outside the `i64` range are skipped.
+fn search(values: &[i64], k: usize, target: i64, visit: &mut dyn FnMut(&[usize]) -> bool) {
+    if k == 2 {
+        let mut seen: HashMap<i64, Vec<usize>> = HashMap::new();
+
+        for (j, &v) in values.iter().enumerate() {
+            let wanted = target.checked_sub(v).and_then(|w| seen.get(&w));
+
+            for &i in wanted.into_iter().flatten() {
+                if !visit(&[i, j]) {
+                    return;
+                }
+            }
+
+            seen.entry(v).or_default().push(j);
+        }
+
+        return;
+    }
+
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by_key(|&i| values[i]);
+
+    let sorted = Sorted {
+        values: order.iter().map(|&i| values[i]).collect(),
+        order,
+    };
+
+    sorted.search(0, k, target, &mut vec![], visit);
+}
+
+/// The entries in ascending order, remembering where each one came from
+struct Sorted {
+    values: Vec<i64>,
+    order: Vec<usize>,
+}
+
+impl Sorted {
+    /// Returns `false` once `visit` asks to stop
+    fn search(
+        &self,
+        start: usize,
+        k: usize,
+        target: i64,
+        prefix: &mut Vec<usize>,
+        visit: &mut dyn FnMut(&[usize]) -> bool,
+    ) -> bool {
+        let v = &self.values;
+        let n = v.len();
+
+        match k {
+            0 => target != 0 || self.visit(prefix, &[], visit),
+            1 => (start..n)
+                .filter(|&i| v[i] == target)
+                .all(|i| self.visit(prefix, &[i], visit)),
+            2 => self.two_pointer(start, target, prefix, visit),
+            _ => {
+                for i in start..n.saturating_sub(k - 1) {
+                    // The smallest and largest sums still reachable from `i`,
+                    // which only narrow the search when they fit an `i64`
+                    let smallest = checked_sum(&v[i..i + k]);
+                    let largest = checked_sum(&v[n - (k - 1)..]).and_then(|s| s.checked_add(v[i]));
+
+                    if matches!(smallest, Some(s) if s > target) {
+                        break;
+                    }
+                    if matches!(largest, Some(l) if l < target) {
+                        continue;
+                    }
+
+                    let rest = match target.checked_sub(v[i]) {
+                        Some(rest) => rest,
+                        None => continue,
+                    };
+
+                    prefix.push(i);
+                    let go_on = self.search(i + 1, k - 1, rest, prefix, visit);
+                    prefix.pop();
+
+                    if !go_on {
+                        return false;
+                    }
+                }
+
+                true
+            }
+        }
+    }
+
+    fn two_pointer(
+        &self,
+        start: usize,
+        target: i64,
+        prefix: &[usize],
+        visit: &mut dyn FnMut(&[usize]) -> bool,
+    ) -> bool {
+        let v = &self.values;
+        let (mut lo, mut hi) = (start, v.len().saturating_sub(1));
+
+        while lo < hi {
+            // A pair that overflows is past `target` on the side of its larger entry
+            let order = match v[lo].checked_add(v[hi]) {
+                Some(sum) => sum.cmp(&target),
+                None if v[hi] > 0 => Ordering::Greater,
+                None => Ordering::Less,
+            };
+
+            if order == Ordering::Less {
+                lo += 1;
+            } else if order == Ordering::Greater {
+                hi -= 1;
+            } else if v[lo] == v[hi] {
+                // Every pair from the run of equal values matches
+                for a in lo..=hi {
+                    for b in a + 1..=hi {
+                        if !self.visit(prefix, &[a, b], visit) {
+                            return false;
+                        }
+                    }
+                }
+                break;
+            } else {
+                let lo_end = lo + v[lo..].iter().take_while(|&&x| x == v[lo]).count();
+                let hi_start = hi + 1 - v[..=hi].iter().rev().take_while(|&&x| x == v[hi]).count();
+
+                for a in lo..lo_end {
+                    for b in hi_start..=hi {
+                        if !self.visit(prefix, &[a, b], visit) {
+                            return false;
+                        }
+                    }
+                }
+
+                lo = lo_end;
+                hi = hi_start - 1;
+            }
+        }
+
+        true
+    }
+
+    /// Map sorted positions back to input indices before visiting
+    fn visit(
+        &self,
+        prefix: &[usize],
+        rest: &[usize],
+        visit: &mut dyn FnMut(&[usize]) -> bool,
+    ) -> bool {
+        let mut t: Vec<usize> = prefix.iter().chain(rest).map(|&p| self.order[p]).collect();
+        t.sort_unstable();
+
+        visit(&t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn k_sum_test() {
+        let values = [1721, 979, 366, 299, 675, 1456];
+
+        assert_eq!(k_sum(&values, 2, TARGET), [[0, 3]]);
+        assert_eq!(k_sum(&values, 3, TARGET), [[1, 2, 4]]);
+        assert_eq!(first_k_sum(&values, 4, TARGET), None);
+        assert_eq!(first_k_sum(&[], 2, TARGET), None);
+        assert_eq!(first_k_sum(&[], 3, TARGET), None);
+
+        // Repeated values give one tuple per choice of indices
+        let values = [5, 5, 5, 0, 10, 10];
+        assert_eq!(k_sum(&values, 2, 10).len(), 3 + 2);
+        assert_eq!(k_sum(&values, 3, 20).len(), 2 * 3 + 1);
+        assert_eq!(k_sum(&values, 4, 20).len(), 3 * 2);
+
+        // Entries near the ends of the range are searched without overflowing
+        let values = [i64::MAX, i64::MAX - 1, i64::MIN, -1, 2021];
+        assert_eq!(k_sum(&values, 2, TARGET), [[3, 4]]);
+        assert_eq!(k_sum(&values, 3, -3), [[1, 2, 3]]);
+        assert_eq!(product(&values, &[3, 4]).unwrap(), -2021);
+        assert!(product(&values, &[0, 4]).is_err());
+    }
+}
@@ -1,63 +1,54 @@
-use std::io::{self, Read, Write};
+const USAGE: &str = "Usage: aoc01 [--k <k>] [--target <sum>] [--all] < input";
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+/// With no arguments, solve both parts; otherwise list the groups of `--k`
+/// entries (default 2) summing to `--target` (default 2020)
+fn main() -> aoc_core::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
-
-    part1(&input)?;
-    part2(&input)?;
-    Ok(())
-}
-
-fn part1(input: &str) -> Result<()> {
-    let mut values = vec![];
-
-    for line in input.lines() {
-        let v: i32 = line.parse()?;
-        values.push(v);
+    if args.is_empty() {
+        return aoc_core::main::<aoc01::Day01>();
     }
 
-    let n = values.len();
-
-    for i in 0..n - 1 {
-        for j in i + 1..n {
-            let x = values[i];
-            let y = values[j];
-
-            if x + y == 2020 {
-                println!("part 1 solution: {}", x * y);
-                return Ok(());
-            }
+    let mut k = 2;
+    let mut target = aoc01::TARGET;
+    let mut all = false;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--k" => k = it.next().ok_or(USAGE)?.parse()?,
+            "--target" => target = it.next().ok_or(USAGE)?.parse()?,
+            "--all" => all = true,
+            _ => return Err(USAGE.into()),
         }
     }
 
-    Ok(())
-}
-
-fn part2(input: &str) -> Result<()> {
-    let mut values = vec![];
-
-    for line in input.lines() {
-        let v: i32 = line.parse()?;
-        values.push(v);
+    let input = aoc_core::input::read_stdin()?;
+    let values: Vec<i64> = aoc_core::input::parse_lines(&input)?;
+
+    let found = if all {
+        aoc01::k_sum(&values, k, target)
+    } else {
+        aoc01::first_k_sum(&values, k, target).into_iter().collect()
+    };
+
+    for t in &found {
+        let lines: Vec<_> = t.iter().map(|i| (i + 1).to_string()).collect();
+        let terms: Vec<_> = t.iter().map(|&i| values[i].to_string()).collect();
+        let product = aoc01::product(&values, t)?;
+
+        println!(
+            "lines {}: {} = {}, product {}",
+            lines.join(", "),
+            terms.join(" + "),
+            target,
+            product
+        );
     }
 
-    let n = values.len();
-    for i in 0..n - 2 {
-        for j in i + 1..n - 1 {
-            for k in j + 1..n {
-                let x = values[i];
-                let y = values[j];
-                let z = values[k];
-
-                if x + y + z == 2020 {
-                    println!("part 2 solution: {}", x * y * z);
-                    return Ok(());
-                }
-            }
-        }
+    if found.is_empty() {
+        return Err(format!("no {} entries sum to {}", k, target).into());
     }
+
     Ok(())
 }
@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use regex::Regex;
+
+use aoc_core::parse::{parse_at, split_once_at};
+use aoc_core::{ParseError, Result, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<PasswordEntry<'a>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<PasswordEntry<'_>>> {
+        input
+            .lines()
+            .map(|line| PasswordEntry::parse(input, line))
+            .collect()
+    }
+
+    fn part1(entries: &Vec<PasswordEntry>) -> Result<usize> {
+        Ok(count_valid(entries, &CountInRange))
+    }
+
+    fn part2(entries: &Vec<PasswordEntry>) -> Result<usize> {
+        Ok(count_valid(entries, &ExactlyOnePosition))
+    }
+}
+
+/// One line of the database, e.g. `1-3 a: abcde`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordEntry<'a> {
+    pub low: usize,
+    pub high: usize,
+    pub letter: char,
+    pub password: &'a str,
+}
+
+impl<'a> PasswordEntry<'a> {
+    pub fn parse(input: &str, line: &'a str) -> Result<PasswordEntry<'a>> {
+        let (policy, password) = split_once_at(input, line, ": ")?;
+        let (range, letter) = split_once_at(input, policy, " ")?;
+        let (low, high) = split_once_at(input, range, "-")?;
+
+        let mut chars = letter.chars();
+        let letter = match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => return Err(ParseError::at(input, letter, "a single letter").into()),
+        };
+
+        Ok(PasswordEntry {
+            low: parse_at(input, low, "a number")?,
+            high: parse_at(input, high, "a number")?,
+            letter,
+            password,
+        })
+    }
+}
+
+/// A rule a password has to follow
+pub trait Policy {
+    /// The name the policy is selected by
+    fn name(&self) -> String;
+
+    /// Why `entry` breaks the policy, or `None` if it follows it
+    fn violation(&self, entry: &PasswordEntry) -> Option<String>;
+}
+
+/// The sled rental policy: the letter appears between `low` and `high` times
+pub struct CountInRange;
+
+impl Policy for CountInRange {
+    fn name(&self) -> String {
+        "count".to_string()
+    }
+
+    fn violation(&self, e: &PasswordEntry) -> Option<String> {
+        let cnt = e.password.matches(e.letter).count();
+
+        if cnt < e.low || cnt > e.high {
+            Some(format!(
+                "`{}` appears {} times, expected {} to {}",
+                e.letter, cnt, e.low, e.high
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// The toboggan policy: exactly one of the 1-based positions `low` and `high`
+/// holds the letter
+pub struct ExactlyOnePosition;
+
+impl Policy for ExactlyOnePosition {
+    fn name(&self) -> String {
+        "position".to_string()
+    }
+
+    fn violation(&self, e: &PasswordEntry) -> Option<String> {
+        // Anything outside the password cannot match
+        let at = |p: usize| p.checked_sub(1).and_then(|i| e.password.chars().nth(i));
+
+        match (at(e.low) == Some(e.letter), at(e.high) == Some(e.letter)) {
+            (true, false) | (false, true) => None,
+            (true, true) => Some(format!(
+                "`{}` is at both positions {} and {}",
+                e.letter, e.low, e.high
+            )),
+            (false, false) => Some(format!(
+                "`{}` is at neither position {} nor {}",
+                e.letter, e.low, e.high
+            )),
+        }
+    }
+}
+
+/// The password matches a regular expression
+pub struct Matches(pub Regex);
+
+impl Policy for Matches {
+    fn name(&self) -> String {
+        format!("regex:{}", self.0)
+    }
+
+    fn violation(&self, e: &PasswordEntry) -> Option<String> {
+        if self.0.is_match(e.password) {
+            None
+        } else {
+            Some(format!("`{}` does not match `{}`", e.password, self.0))
+        }
+    }
+}
+
+/// The password carries at least this many bits of Shannon entropy, judged
+/// from its own character frequencies
+pub struct MinEntropy(pub f64);
+
+/// Total bits of Shannon entropy in `s`
+pub fn entropy(s: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in s.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+
+    let len = s.chars().count() as f64;
+
+    counts
+        .values()
+        .map(|&n| -(n as f64) * (n as f64 / len).log2())
+        .sum()
+}
+
+impl Policy for MinEntropy {
+    fn name(&self) -> String {
+        format!("entropy:{}", self.0)
+    }
+
+    fn violation(&self, e: &PasswordEntry) -> Option<String> {
+        let bits = entropy(e.password);
+
+        if bits < self.0 {
+            Some(format!(
+                "`{}` has {:.1} bits of entropy, expected at least {}",
+                e.password, bits, self.0
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+type CharClass = (&'static str, fn(char) -> bool);
+
+const CHAR_CLASSES: [CharClass; 4] = [
+    ("lowercase", char::is_lowercase),
+    ("uppercase", char::is_uppercase),
+    ("digits", |c| c.is_ascii_digit()),
+    ("symbols", |c| !c.is_alphanumeric()),
+];
+
+/// The password mixes at least this many of lowercase letters, uppercase
+/// letters, digits and symbols
+pub struct CharClasses(pub usize);
+
+impl Policy for CharClasses {
+    fn name(&self) -> String {
+        format!("classes:{}", self.0)
+    }
+
+    fn violation(&self, e: &PasswordEntry) -> Option<String> {
+        let used: Vec<&str> = CHAR_CLASSES
+            .iter()
+            .filter(|(_, is)| e.password.chars().any(is))
+            .map(|(name, _)| *name)
+            .collect();
+
+        if used.len() < self.0 {
+            Some(format!(
+                "`{}` uses {} character classes ({}), expected at least {}",
+                e.password,
+                used.len(),
+                used.join(", "),
+                self.0
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+type Constructor = fn(Option<&str>) -> Result<Box<dyn Policy>>;
+
+/// A policy that can be selected by name
+pub struct Registered {
+    pub name: &'static str,
+    /// What follows `name:`, for policies that take an argument
+    pub arg: Option<&'static str>,
+    pub about: &'static str,
+    build: Constructor,
+}
+
+impl Registered {
+    pub fn usage(&self) -> String {
+        match self.arg {
+            Some(arg) => format!("{}:{} - {}", self.name, arg, self.about),
+            None => format!("{} - {}", self.name, self.about),
+        }
+    }
+}
+
+pub static POLICIES: &[Registered] = &[
+    Registered {
+        name: "count",
+        arg: None,
+        about: "the letter appears between low and high times (part 1)",
+        build: |_| Ok(Box::new(CountInRange)),
+    },
+    Registered {
+        name: "position",
+        arg: None,
+        about: "exactly one of positions low and high holds the letter (part 2)",
+        build: |_| Ok(Box::new(ExactlyOnePosition)),
+    },
+    Registered {
+        name: "regex",
+        arg: Some("PATTERN"),
+        about: "the password matches PATTERN",
+        build: |arg| Ok(Box::new(Matches(Regex::new(arg.unwrap_or_default())?))),
+    },
+    Registered {
+        name: "entropy",
+        arg: Some("BITS"),
+        about: "the password has at least BITS bits of entropy",
+        build: |arg| Ok(Box::new(MinEntropy(arg.unwrap_or_default().parse()?))),
+    },
+    Registered {
+        name: "classes",
+        arg: Some("N"),
+        about: "at least N of lowercase, uppercase, digits and symbols",
+        build: |arg| Ok(Box::new(CharClasses(arg.unwrap_or_default().parse()?))),
+    },
+];
+
+/// Build a policy from `name` or `name:argument`
+pub fn policy(spec: &str) -> Result<Box<dyn Policy>> {
+    let (name, arg) = match spec.split_once(':') {
+        Some((name, arg)) => (name, Some(arg)),
+        None => (spec, None),
+    };
+
+    let registered = POLICIES
+        .iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("unknown policy `{}`", name))?;
+
+    if registered.arg.is_some() != arg.is_some() {
+        return Err(format!("expected policy `{}`", registered.usage()).into());
+    }
+
+    (registered.build)(arg).map_err(|e| format!("bad policy `{}`: {}", spec, e).into())
+}
+
+/// A password that broke one of the policies it was checked against
+#[derive(Debug, PartialEq)]
+pub struct Violation {
+    /// 0-based index of the entry
+    pub entry: usize,
+    pub policy: String,
+    pub reason: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: {}: {}",
+            self.entry + 1,
+            self.policy,
+            self.reason
+        )
+    }
+}
+
+/// Every rule each entry breaks, in entry order
+pub fn audit(entries: &[PasswordEntry], policies: &[Box<dyn Policy>]) -> Vec<Violation> {
+    entries
+        .iter()
+        .enumerate()
+        .flat_map(|(i, e)| {
+            policies.iter().filter_map(move |p| {
+                p.violation(e).map(|reason| Violation {
+                    entry: i,
+                    policy: p.name(),
+                    reason,
+                })
+            })
+        })
+        .collect()
+}
+
+pub fn count_valid(entries: &[PasswordEntry], policy: &dyn Policy) -> usize {
+    entries
+        .iter()
+        .filter(|e| policy.violation(e).is_none())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_test() {
+        let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+        let entries = Day02::parse(input).unwrap();
+
+        let policies = vec![policy("count").unwrap(), policy("position").unwrap()];
+        let violations = audit(&entries, &policies);
+
+        assert_eq!(violations.len(), 3);
+        assert_eq!(
+            violations[0].to_string(),
+            "line 2: count: `b` appears 0 times, expected 1 to 3"
+        );
+        assert_eq!(violations[2].policy, "position");
+
+        let e = PasswordEntry::parse("1-1 a: aB3$", "1-1 a: aB3$").unwrap();
+        assert!(policy("classes:4").unwrap().violation(&e).is_none());
+        assert!(policy("classes:5").unwrap().violation(&e).is_some());
+        assert!(policy("regex:^[a-z]+$").unwrap().violation(&e).is_some());
+        assert!(policy("entropy:8").unwrap().violation(&e).is_none());
+        assert_eq!(entropy("aaaa"), 0.0);
+
+        assert!(policy("entropy").is_err());
+        assert!(policy("count:3").is_err());
+        assert!(policy("length").is_err());
+    }
+}
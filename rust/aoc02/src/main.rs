@@ -1,109 +1,48 @@
-use std::io::{self, Read};
+use aoc_core::Solution;
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+const USAGE: &str = "Usage: aoc02 [--policy <name>]... [--list] < input";
 
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
+/// With no arguments, solve both parts; each `--policy` adds a policy to audit
+/// every password against, reporting each rule it breaks
+fn main() -> aoc_core::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-    let start = std::time::Instant::now();
-    part1(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
-
-    let start = std::time::Instant::now();
-    part1_v2(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
-
-    let start = std::time::Instant::now();
-    part2(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
-
-    Ok(())
-}
-
-fn part1(input: &str) -> Result<()> {
-    let mut validated_passwords = 0;
-
-    for line in input.lines() {
-        let lx = line.split_whitespace().take(3).collect::<Vec<&str>>();
-        let mut p = lx[0].split("-").map(|s| s.parse().unwrap());
-        let low: i32 = p.next().unwrap();
-        let high: i32 = p.next().unwrap();
-
-        let target = lx[1].trim().replace(":", "").parse::<char>()?;
-        let password = lx[2].trim();
-
-        let mut cnt = 0;
+    if args.is_empty() {
+        return aoc_core::main::<aoc02::Day02>();
+    }
 
-        for c in password.chars() {
-            if c == target {
-                cnt += 1;
+    let mut policies = vec![];
 
-                if cnt > high {
-                    break;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--policy" => policies.push(aoc02::policy(it.next().ok_or(USAGE)?)?),
+            "--list" => {
+                for p in aoc02::POLICIES {
+                    println!("{}", p.usage());
                 }
+                return Ok(());
             }
-        }
-
-        if cnt >= low && cnt <= high {
-            validated_passwords += 1;
+            _ => return Err(USAGE.into()),
         }
     }
-    println!("part 1 solution: {}", validated_passwords);
-    Ok(())
-}
-
-fn part1_v2(input: &str) -> Result<()> {
-    let mut validated_passwords = 0;
 
-    for line in input.lines() {
-        let lx = line.split_whitespace().take(3).collect::<Vec<&str>>();
-        let mut p = lx[0].split("-").map(|s| s.parse().unwrap());
-        let low: usize = p.next().unwrap();
-        let high: usize = p.next().unwrap();
+    let input = aoc_core::input::read_stdin()?;
+    let entries = aoc02::Day02::parse(&input)?;
+    let violations = aoc02::audit(&entries, &policies);
 
-        let target = lx[1].trim().replace(":", "").parse::<char>()?;
-        let password = lx[2].trim();
-
-        let cnt = password.matches(target).count();
-
-        if cnt >= low && cnt <= high {
-            validated_passwords += 1;
-        }
+    for v in &violations {
+        println!("{}", v);
     }
-    println!("part 1 solution: {}", validated_passwords);
-    Ok(())
-}
-
-fn part2(input: &str) -> Result<()> {
-    let mut validated_passwords = 0;
-
-    for line in input.lines() {
-        let lx = line.split_whitespace().take(3).collect::<Vec<&str>>();
-        let mut p = lx[0].split("-").map(|s| s.parse::<usize>().unwrap());
-        let p0: usize = p.next().unwrap() - 1;
-        let p1: usize = p.next().unwrap() - 1;
 
-        let target = lx[1].trim().replace(":", "").parse::<char>()?;
-        let password = lx[2].trim();
+    let mut failed: Vec<_> = violations.iter().map(|v| v.entry).collect();
+    failed.dedup();
 
-        let mut cnt = 0;
+    println!(
+        "{} of {} passwords follow every policy",
+        entries.len() - failed.len(),
+        entries.len()
+    );
 
-        let a = password.chars().nth(p0).unwrap();
-        let b = password.chars().nth(p1).unwrap();
-
-        if a == target {
-            cnt += 1
-        }
-
-        if b == target {
-            cnt += 1
-        }
-
-        if cnt == 1 {
-            validated_passwords += 1;
-        }
-    }
-    println!("part 2 solution: {}", validated_passwords);
     Ok(())
 }
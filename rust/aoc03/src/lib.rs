@@ -0,0 +1,131 @@
+use aoc_core::grid::{Bounds, Grid, Pos};
+use aoc_core::{Result, Solution};
+
+/// The slopes checked in part 2, as `(right, down)`
+pub const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+/// The trees on a slope, which repeat forever to the right
+#[derive(Debug, Clone)]
+pub struct TreeMap {
+    /// `true` where there is a tree
+    trees: Grid<bool>,
+}
+
+impl TreeMap {
+    pub fn parse(input: &str) -> Result<TreeMap> {
+        let trees = Grid::parse(input, "`.` or `#`", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+
+        Ok(TreeMap { trees })
+    }
+
+    /// Where the toboggan lands going `right`, `down` at a time from the top
+    /// left until it passes the bottom row, with columns wrapped onto the map.
+    /// Panics if `down` is 0.
+    pub fn path(&self, right: usize, down: usize) -> Vec<Pos> {
+        assert!(down > 0, "the toboggan has to go down");
+
+        self.trees
+            .ray((0, 0), (down as isize, right as isize), Bounds::Wrap)
+            .take((self.trees.height() - 1) / down)
+            .collect()
+    }
+
+    /// Trees hit on the slope of `right` across for every `down`
+    pub fn trees_on_slope(&self, right: usize, down: usize) -> usize {
+        self.path(right, down)
+            .into_iter()
+            .filter(|&p| self.trees[p])
+            .count()
+    }
+
+    /// The `(right, down)` slope with the fewest trees, searching every right
+    /// up to `max_right` and down up to `max_down`; the earliest wins ties.
+    /// Slopes that reach the bottom in a single step are left out.
+    pub fn optimal_slope(
+        &self,
+        max_right: usize,
+        max_down: usize,
+    ) -> Option<((usize, usize), usize)> {
+        let max_down = max_down.min(self.trees.height().saturating_sub(2));
+
+        (1..=max_down)
+            .flat_map(|down| (0..=max_right).map(move |right| (right, down)))
+            .map(|(right, down)| ((right, down), self.trees_on_slope(right, down)))
+            .min_by_key(|&(_, trees)| trees)
+    }
+
+    /// The map, repeated to the right as far as the path goes, with each
+    /// landing marked `X` on a tree and `O` in the open
+    pub fn render(&self, right: usize, down: usize) -> String {
+        let width = self.trees.width();
+        let steps = self.path(right, down).len();
+        let copies = (steps * right) / width + 1;
+
+        let mut rows: Vec<Vec<char>> = self
+            .trees
+            .rows()
+            .map(|row| {
+                let row: Vec<char> = row.iter().map(|&t| if t { '#' } else { '.' }).collect();
+                row.repeat(copies)
+            })
+            .collect();
+
+        for k in 1..=steps {
+            let cell = &mut rows[k * down][k * right];
+            *cell = if *cell == '#' { 'X' } else { 'O' };
+        }
+
+        rows.iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'a> = TreeMap;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<TreeMap> {
+        TreeMap::parse(input)
+    }
+
+    fn part1(map: &TreeMap) -> Result<usize> {
+        Ok(map.trees_on_slope(3, 1))
+    }
+
+    fn part2(map: &TreeMap) -> Result<usize> {
+        let soln = SLOPES
+            .iter()
+            .map(|&(right, down)| map.trees_on_slope(right, down))
+            .product();
+
+        Ok(soln)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_test() {
+        let map = TreeMap::parse(include_str!("../examples/example.txt")).unwrap();
+
+        let render = map.render(3, 1);
+        let rows: Vec<&str> = render.lines().collect();
+        assert_eq!(rows[1], "#..O#...#..#...#...#..#...#...#..");
+        assert_eq!(render.matches('X').count(), 7);
+        assert_eq!(render.matches('O').count(), 3);
+
+        assert_eq!(map.trees_on_slope(1, 2), 2);
+        assert_eq!(map.optimal_slope(3, 2), Some(((2, 1), 1)));
+    }
+}
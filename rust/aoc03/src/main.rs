@@ -1,56 +1,66 @@
-use std::io::{self, Read};
+const USAGE: &str =
+    "Usage: aoc03 [--slope <right>/<down> [--render]] [--optimal <right>/<down>] < input";
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+fn parse_slope(arg: Option<&String>) -> aoc_core::Result<(usize, usize)> {
+    let (right, down) = arg.and_then(|a| a.split_once('/')).ok_or(USAGE)?;
+    let down = down.parse()?;
 
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
+    if down == 0 {
+        return Err("the toboggan has to go down".into());
+    }
 
-    let start = std::time::Instant::now();
-    part1(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
+    Ok((right.parse()?, down))
+}
 
-    let start = std::time::Instant::now();
-    part2(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
+/// With no arguments, solve both parts; `--slope` counts the trees on one
+/// slope (and draws its path with `--render`), `--optimal` searches every
+/// slope up to the given one for the fewest trees
+fn main() -> aoc_core::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-    Ok(())
-}
+    if args.is_empty() {
+        return aoc_core::main::<aoc03::Day03>();
+    }
 
-fn part1(input: &str) -> Result<()> {
-    let tree = "#".chars().nth(0);
-    let num_trees = input
-        .lines()
-        .skip(1)
-        .enumerate()
-        .map(|(i, l)| l.chars().nth(3 * (i + 1) % l.len()))
-        .filter(|x| x == &tree)
-        .count();
+    let mut slope = None;
+    let mut optimal = None;
+    let mut render = false;
 
-    println!("part 1 solution: {}", num_trees);
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--slope" => slope = Some(parse_slope(it.next())?),
+            "--optimal" => optimal = Some(parse_slope(it.next())?),
+            "--render" => render = true,
+            _ => return Err(USAGE.into()),
+        }
+    }
 
-    Ok(())
-}
+    let input = aoc_core::input::read_stdin()?;
+    let map = aoc03::TreeMap::parse(&input)?;
+
+    if let Some((right, down)) = slope {
+        if render {
+            println!("{}\n", map.render(right, down));
+        }
+        println!(
+            "right {}, down {}: {} trees",
+            right,
+            down,
+            map.trees_on_slope(right, down)
+        );
+    }
+
+    if let Some((max_right, max_down)) = optimal {
+        let ((right, down), trees) = map
+            .optimal_slope(max_right, max_down)
+            .ok_or("the map is too short to search")?;
 
-fn part2(input: &str) -> Result<()> {
-    let tree = "#".chars().nth(0);
-    let paths = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
-
-    let soln: usize = paths
-        .iter()
-        .map(|(st_right, st_down)| {
-            input
-                .lines()
-                .skip(*st_down as usize)
-                .step_by(*st_down as usize)
-                .enumerate()
-                .map(|(i, l)| l.chars().nth((st_right * (i + 1)) % l.len()))
-                .filter(|x| x == &tree)
-                .count()
-        })
-        .product();
-
-    println!("part 2 solution: {:?}", soln);
+        println!(
+            "fewest trees: right {}, down {}: {} trees",
+            right, down, trees
+        );
+    }
 
     Ok(())
 }
@@ -0,0 +1,49 @@
+use aoc_core::{Result, Solution};
+
+use crate::schema::{Record, Schema};
+
+pub mod schema;
+
+/// The passport fields, as a schema
+pub const PASSPORT_SCHEMA: &str = include_str!("../passport.toml");
+
+/// The passport records, still to be checked against the schema
+pub struct Passports<'a> {
+    pub schema: Schema,
+    pub records: Vec<Record<'a>>,
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'a> = Passports<'a>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Passports<'_>> {
+        Ok(Passports {
+            schema: Schema::from_toml(PASSPORT_SCHEMA)?,
+            records: schema::parse_records(input)?,
+        })
+    }
+
+    fn part1(passports: &Passports) -> Result<usize> {
+        let schema = &passports.schema;
+
+        Ok(passports
+            .records
+            .iter()
+            .filter(|r| schema.is_complete(r))
+            .count())
+    }
+
+    fn part2(passports: &Passports) -> Result<usize> {
+        let schema = &passports.schema;
+
+        Ok(passports
+            .records
+            .iter()
+            .filter(|r| schema.check(r).is_valid())
+            .count())
+    }
+}
@@ -0,0 +1,314 @@
+//! Checking blank-line separated `key:value` records against a schema of
+//! field rules, read from TOML or JSON
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use aoc_core::parse::split_once_at;
+use aoc_core::{input, Result};
+
+/// A field as written in a schema file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FieldSpec {
+    name: String,
+    #[serde(default = "required_by_default")]
+    required: bool,
+    /// Inclusive bounds of an integer value
+    int: Option<[i64; 2]>,
+    /// Inclusive bounds of an integer followed by one of the units, e.g. `170cm`
+    units: Option<BTreeMap<String, [i64; 2]>>,
+    regex: Option<String>,
+    one_of: Option<Vec<String>>,
+}
+
+fn required_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SchemaFile {
+    field: Vec<FieldSpec>,
+}
+
+#[derive(Debug)]
+enum Rule {
+    Any,
+    Int(i64, i64),
+    Units(BTreeMap<String, [i64; 2]>),
+    Regex(Regex),
+    OneOf(Vec<String>),
+}
+
+impl Rule {
+    /// Why `value` breaks the rule, if it does
+    fn violation(&self, value: &str) -> Option<String> {
+        let in_range = |v: &str, lo: i64, hi: i64| match v.parse::<i64>() {
+            Ok(n) if (lo..=hi).contains(&n) => None,
+            Ok(_) => Some(format!("is not between {} and {}", lo, hi)),
+            Err(_) => Some("is not a number".to_string()),
+        };
+
+        match self {
+            Rule::Any => None,
+            Rule::Int(lo, hi) => in_range(value, *lo, *hi),
+            Rule::Units(units) => {
+                let found = units.iter().find_map(|(unit, range)| {
+                    value.strip_suffix(unit.as_str()).map(|v| (unit, v, range))
+                });
+
+                match found {
+                    Some((unit, v, [lo, hi])) => {
+                        in_range(v, *lo, *hi).map(|reason| format!("{} {}", reason, unit))
+                    }
+                    None => {
+                        let names: Vec<_> = units.keys().map(|u| u.as_str()).collect();
+                        Some(format!("has no unit ({})", names.join(", ")))
+                    }
+                }
+            }
+            Rule::Regex(re) if re.is_match(value) => None,
+            Rule::Regex(re) => Some(format!("does not match `{}`", re)),
+            Rule::OneOf(choices) if choices.iter().any(|c| c == value) => None,
+            Rule::OneOf(choices) => Some(format!("is not one of {}", choices.join(", "))),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Field {
+    name: String,
+    required: bool,
+    rule: Rule,
+}
+
+#[derive(Debug)]
+pub struct Schema {
+    fields: Vec<Field>,
+}
+
+impl Schema {
+    pub fn from_toml(s: &str) -> Result<Schema> {
+        Schema::from_file(toml::from_str(s)?)
+    }
+
+    pub fn from_json(s: &str) -> Result<Schema> {
+        Schema::from_file(serde_json::from_str(s)?)
+    }
+
+    /// Read a `.json` schema, or TOML for any other extension
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Schema> {
+        let path = path.as_ref();
+        let s = input::read_file(path)?;
+
+        let schema = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Schema::from_json(&s),
+            _ => Schema::from_toml(&s),
+        };
+
+        schema.map_err(|e| format!("bad schema {}: {}", path.display(), e).into())
+    }
+
+    fn from_file(file: SchemaFile) -> Result<Schema> {
+        let fields = file
+            .field
+            .into_iter()
+            .map(|f| {
+                let mut rules = vec![];
+
+                if let Some([lo, hi]) = f.int {
+                    rules.push(Rule::Int(lo, hi));
+                }
+                if let Some(units) = f.units {
+                    rules.push(Rule::Units(units));
+                }
+                if let Some(re) = f.regex {
+                    rules.push(Rule::Regex(Regex::new(&re)?));
+                }
+                if let Some(choices) = f.one_of {
+                    rules.push(Rule::OneOf(choices));
+                }
+
+                if rules.len() > 1 {
+                    return Err(format!("field `{}` has more than one rule", f.name).into());
+                }
+
+                Ok(Field {
+                    name: f.name,
+                    required: f.required,
+                    rule: rules.pop().unwrap_or(Rule::Any),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Schema { fields })
+    }
+
+    /// Every required field is present, whatever its value, without checking
+    /// any of them; the same as `check(record).is_complete()`
+    pub fn is_complete(&self, record: &Record) -> bool {
+        self.fields
+            .iter()
+            .all(|f| !f.required || record.fields.contains_key(f.name.as_str()))
+    }
+
+    pub fn check(&self, record: &Record) -> RecordReport {
+        let fields = self
+            .fields
+            .iter()
+            .map(|f| {
+                let status = match record.fields.get(f.name.as_str()) {
+                    None if f.required => Status::Missing,
+                    None => Status::Absent,
+                    Some(v) => match f.rule.violation(v) {
+                        None => Status::Valid,
+                        Some(reason) => Status::Invalid(format!("`{}` {}", v, reason)),
+                    },
+                };
+
+                FieldReport {
+                    name: f.name.clone(),
+                    status,
+                }
+            })
+            .collect();
+
+        RecordReport {
+            line: record.line,
+            fields,
+        }
+    }
+}
+
+/// One blank-line separated group of `key:value` pairs
+#[derive(Debug)]
+pub struct Record<'a> {
+    /// 1-based line the record starts on
+    pub line: usize,
+    pub fields: HashMap<&'a str, &'a str>,
+}
+
+pub fn parse_records(input: &str) -> Result<Vec<Record<'_>>> {
+    let mut records = vec![];
+    let mut current: Option<Record> = None;
+
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            records.extend(current.take());
+            continue;
+        }
+
+        let record = current.get_or_insert_with(|| Record {
+            line: i + 1,
+            fields: HashMap::new(),
+        });
+
+        for pair in line.split_whitespace() {
+            let (k, v) = split_once_at(input, pair, ":")?;
+            record.fields.insert(k, v);
+        }
+    }
+
+    records.extend(current);
+
+    Ok(records)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Valid,
+    /// An optional field that is not there
+    Absent,
+    /// A required field that is not there
+    Missing,
+    Invalid(String),
+}
+
+#[derive(Debug)]
+pub struct FieldReport {
+    pub name: String,
+    pub status: Status,
+}
+
+/// How every field of the schema fared in one record
+#[derive(Debug)]
+pub struct RecordReport {
+    pub line: usize,
+    pub fields: Vec<FieldReport>,
+}
+
+impl RecordReport {
+    /// Every required field is present, whatever its value
+    pub fn is_complete(&self) -> bool {
+        self.fields.iter().all(|f| f.status != Status::Missing)
+    }
+
+    /// Every required field is present with a valid value
+    pub fn is_valid(&self) -> bool {
+        self.fields
+            .iter()
+            .all(|f| matches!(f.status, Status::Valid | Status::Absent))
+    }
+}
+
+impl fmt::Display for RecordReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.is_valid() { "valid" } else { "invalid" };
+        write!(f, "record at line {}: {}", self.line, verdict)?;
+
+        for field in &self.fields {
+            let status = match &field.status {
+                Status::Valid => "ok",
+                Status::Absent => "absent",
+                Status::Missing => "missing",
+                Status::Invalid(reason) => reason,
+            };
+
+            write!(f, "\n  {}: {}", field.name, status)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_test() {
+        let schema = Schema::from_json(
+            r#"{"field": [
+                {"name": "id", "regex": "^[0-9]+$"},
+                {"name": "size", "units": {"kg": [1, 5]}},
+                {"name": "tag", "required": false}
+            ]}"#,
+        )
+        .unwrap();
+
+        let records = parse_records("id:12 size:3kg\n\nid:x\nsize:9kg\n\ntag:a\n").unwrap();
+        let reports: Vec<_> = records.iter().map(|r| schema.check(r)).collect();
+
+        assert!(reports[0].is_valid());
+        assert!(reports[1].is_complete() && !reports[1].is_valid());
+        assert!(!reports[2].is_complete());
+
+        assert_eq!(
+            reports[1].to_string(),
+            "record at line 3: invalid\n  \
+             id: `x` does not match `^[0-9]+$`\n  \
+             size: `9kg` is not between 1 and 5 kg\n  \
+             tag: absent"
+        );
+
+        assert!(
+            Schema::from_toml("[[field]]\nname = \"a\"\nint = [1, 2]\nregex = \"b\"\n").is_err()
+        );
+        assert!(parse_records("id:1 size\n").is_err());
+    }
+}
@@ -0,0 +1,389 @@
+use std::fmt;
+
+use aoc_core::{ParseError, Result, Solution};
+
+/// A seat position, counted from 0 at the front left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Seat {
+    pub row: u32,
+    pub col: u32,
+}
+
+/// Converts between seats and boarding passes for a plane with a power of two
+/// rows and columns, and no more seats than a `u32` id can number. A pass names the row and then the column by binary space
+/// partitioning: each letter keeps the lower or upper half of what is left.
+#[derive(Debug, Clone)]
+pub struct Codec {
+    rows: u32,
+    cols: u32,
+    /// Lower and upper half letters, for rows then columns
+    letters: [char; 4],
+}
+
+impl Codec {
+    /// `letters` are the row lower/upper and column lower/upper letters, e.g.
+    /// `['F', 'B', 'L', 'R']`
+    pub fn new(rows: u32, cols: u32, letters: [char; 4]) -> Result<Codec> {
+        if !rows.is_power_of_two() || !cols.is_power_of_two() {
+            return Err(format!("{} rows by {} columns is not a power of two", rows, cols).into());
+        }
+
+        if rows.checked_mul(cols).is_none() {
+            return Err(format!(
+                "{} rows by {} columns is too many seats to number",
+                rows, cols
+            )
+            .into());
+        }
+
+        if letters[0] == letters[1] || letters[2] == letters[3] {
+            return Err("each half needs its own letter".into());
+        }
+
+        Ok(Codec {
+            rows,
+            cols,
+            letters,
+        })
+    }
+
+    /// 128 rows of 8 seats, passes like `FBFBBFFRLR`
+    pub fn standard() -> Codec {
+        Codec {
+            rows: 128,
+            cols: 8,
+            letters: ['F', 'B', 'L', 'R'],
+        }
+    }
+
+    fn row_bits(&self) -> usize {
+        self.rows.trailing_zeros() as usize
+    }
+
+    fn col_bits(&self) -> usize {
+        self.cols.trailing_zeros() as usize
+    }
+
+    /// Characters in a boarding pass
+    pub fn len(&self) -> usize {
+        self.row_bits() + self.col_bits()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn check(&self, seat: Seat) -> Result<()> {
+        if seat.row >= self.rows || seat.col >= self.cols {
+            return Err(format!(
+                "there is no seat at row {}, column {} on a plane of {} by {}",
+                seat.row, seat.col, self.rows, self.cols
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    pub fn seat_id(&self, seat: Seat) -> Result<u32> {
+        self.check(seat)?;
+
+        Ok(seat.row * self.cols + seat.col)
+    }
+
+    /// The lower and upper letters for the `n`th character of a pass
+    fn letters(&self, n: usize) -> (char, char) {
+        if n < self.row_bits() {
+            (self.letters[0], self.letters[1])
+        } else {
+            (self.letters[2], self.letters[3])
+        }
+    }
+
+    /// The half each letter of `pass` (a line of `input`) keeps, `true` for
+    /// the upper one
+    fn halves<'a>(&'a self, input: &str, pass: &'a str) -> Result<impl Iterator<Item = bool> + 'a> {
+        let mut n = 0;
+
+        for (i, c) in pass.char_indices() {
+            let found = &pass[i..i + c.len_utf8()];
+            if n == self.len() {
+                return Err(ParseError::at(input, found, "the end of the line").into());
+            }
+
+            let (lower, upper) = self.letters(n);
+            if c != lower && c != upper {
+                let expected = format!("`{}` or `{}`", lower, upper);
+                return Err(ParseError::at(input, found, expected).into());
+            }
+
+            n += 1;
+        }
+
+        if n < self.len() {
+            let expected = format!("{} seat characters", self.len());
+            return Err(ParseError::at(input, &pass[pass.len()..], expected).into());
+        }
+
+        Ok(pass
+            .chars()
+            .enumerate()
+            .map(move |(n, c)| c == self.letters(n).1))
+    }
+
+    /// Decode `pass`, a line of `input`, by narrowing down the rows and then
+    /// the columns one letter at a time
+    pub fn decode(&self, input: &str, pass: &str) -> Result<Seat> {
+        let (mut row_low, mut row_high) = (0, self.rows - 1);
+        let (mut col_low, mut col_high) = (0, self.cols - 1);
+
+        for (n, upper) in self.halves(input, pass)?.enumerate() {
+            let (low, high) = if n < self.row_bits() {
+                (&mut row_low, &mut row_high)
+            } else {
+                (&mut col_low, &mut col_high)
+            };
+
+            let mid = *low + (*high - *low) / 2;
+            if upper {
+                *low = mid + 1;
+            } else {
+                *high = mid;
+            }
+        }
+
+        Ok(Seat {
+            row: row_low,
+            col: col_low,
+        })
+    }
+
+    /// Decode `pass` by reading it as a binary number, upper halves being 1s;
+    /// the same answer as `decode` without the bookkeeping
+    pub fn decode_bits(&self, input: &str, pass: &str) -> Result<Seat> {
+        let mut bits = 0;
+        for upper in self.halves(input, pass)? {
+            bits = bits << 1 | upper as u32;
+        }
+
+        Ok(Seat {
+            row: bits >> self.col_bits(),
+            col: bits & (self.cols - 1),
+        })
+    }
+
+    pub fn encode(&self, seat: Seat) -> Result<String> {
+        self.check(seat)?;
+
+        let rows = (0..self.row_bits())
+            .rev()
+            .map(|b| self.letters[(seat.row >> b & 1) as usize]);
+        let cols = (0..self.col_bits())
+            .rev()
+            .map(|b| self.letters[2 + (seat.col >> b & 1) as usize]);
+
+        Ok(rows.chain(cols).collect())
+    }
+
+    /// Every seat in the rows the boarding passes seen reach, given their ids
+    pub fn seat_map(&self, seat_ids: &[u32]) -> SeatMap {
+        let cols = self.cols as usize;
+        let on_plane = || {
+            seat_ids
+                .iter()
+                .map(|&id| id as usize)
+                .filter(|&id| id < self.rows as usize * cols)
+        };
+
+        let (first, last) = match (on_plane().min(), on_plane().max()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => {
+                return SeatMap {
+                    cols,
+                    first_row: 0,
+                    seats: Vec::new(),
+                }
+            }
+        };
+
+        let first_row = first / cols;
+        let offset = first_row * cols;
+        let mut seats = vec![SeatState::Free; (last / cols + 1) * cols - offset];
+
+        for id in on_plane() {
+            seats[id - offset] = SeatState::Occupied;
+        }
+
+        // Seats before the first pass and after the last are not on this plane
+        for (i, s) in seats.iter_mut().enumerate() {
+            if !(first..=last).contains(&(offset + i)) {
+                *s = SeatState::Missing;
+            }
+        }
+
+        SeatMap {
+            cols,
+            first_row,
+            seats,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeatState {
+    Occupied,
+    Free,
+    /// Not on this plane
+    Missing,
+}
+
+/// The state of every seat in a run of whole rows of a flight
+#[derive(Debug)]
+pub struct SeatMap {
+    cols: usize,
+    first_row: usize,
+    /// By seat id, counted from the start of `first_row`
+    pub seats: Vec<SeatState>,
+}
+
+impl SeatMap {
+    pub fn count(&self, state: SeatState) -> usize {
+        self.seats.iter().filter(|&&s| s == state).count()
+    }
+
+    /// The row `seats` starts at
+    pub fn first_row(&self) -> usize {
+        self.first_row
+    }
+
+    /// Free seats whose neighbours on either side are both taken
+    pub fn free_between_occupied(&self) -> Vec<u32> {
+        let offset = self.first_row * self.cols;
+
+        (1..self.seats.len().saturating_sub(1))
+            .filter(|&i| {
+                self.seats[i] == SeatState::Free
+                    && self.seats[i - 1] == SeatState::Occupied
+                    && self.seats[i + 1] == SeatState::Occupied
+            })
+            .map(|i| (offset + i) as u32)
+            .collect()
+    }
+}
+
+/// One line per row: `#` occupied, `.` free and `-` missing
+impl fmt::Display for SeatMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (row, seats) in self.seats.chunks(self.cols).enumerate() {
+            let seats: String = seats
+                .iter()
+                .map(|s| match s {
+                    SeatState::Occupied => '#',
+                    SeatState::Free => '.',
+                    SeatState::Missing => '-',
+                })
+                .collect();
+
+            writeln!(f, "{:>4} {}", self.first_row + row, seats)?;
+        }
+
+        write!(
+            f,
+            "{} occupied, {} free, {} missing",
+            self.count(SeatState::Occupied),
+            self.count(SeatState::Free),
+            self.count(SeatState::Missing)
+        )
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input<'a> = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>> {
+        let codec = Codec::standard();
+
+        input
+            .lines()
+            .map(|line| codec.seat_id(codec.decode_bits(input, line)?))
+            .collect()
+    }
+
+    fn part1(seat_ids: &Vec<u32>) -> Result<u32> {
+        let max_seat_id = seat_ids.iter().copied().max().ok_or("no seats")?;
+
+        Ok(max_seat_id)
+    }
+
+    fn part2(seat_ids: &Vec<u32>) -> Result<u32> {
+        let free = Codec::standard().seat_map(seat_ids).free_between_occupied();
+
+        match free[..] {
+            [id] => Ok(id),
+            [] => Err("no free seat between two occupied ones".into()),
+            _ => Err(format!("{} free seats between occupied ones", free.len()).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part2_test() {
+        assert_eq!(Day05::part2(&vec![13, 12, 15, 16]).unwrap(), 14);
+        assert!(Day05::part2(&vec![]).is_err());
+        assert!(Day05::part2(&vec![3, 5, 7]).is_err());
+    }
+
+    #[test]
+    fn codec_test() {
+        let codec = Codec::standard();
+
+        for &(pass, row, col) in &[
+            ("BFFFBBFRRR", 70, 7),
+            ("FFFBBBFRRR", 14, 7),
+            ("BBFFBBFRLL", 102, 4),
+        ] {
+            let seat = Seat { row, col };
+            assert_eq!(codec.decode(pass, pass).unwrap(), seat);
+            assert_eq!(codec.decode_bits(pass, pass).unwrap(), seat);
+            assert_eq!(codec.encode(seat).unwrap(), pass);
+        }
+
+        let small = Codec::new(4, 2, ['0', '1', 'a', 'b']).unwrap();
+        assert_eq!(small.encode(Seat { row: 2, col: 1 }).unwrap(), "10b");
+        assert_eq!(small.decode("10b", "10b").unwrap(), Seat { row: 2, col: 1 });
+        assert!(small.encode(Seat { row: 4, col: 0 }).is_err());
+        assert!(small.seat_id(Seat { row: 0, col: 2 }).is_err());
+        assert!(Codec::new(100, 8, ['F', 'B', 'L', 'R']).is_err());
+        assert!(Codec::new(1 << 16, 1 << 16, ['F', 'B', 'L', 'R']).is_err());
+        assert!(Codec::new(1 << 16, 1 << 15, ['F', 'B', 'L', 'R']).is_ok());
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let e = Day05::parse("FBFBBFFRLR\nFBFBXFFRLR\n").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 5: expected `F` or `B`, found `X`"
+        );
+
+        let e = Day05::parse("FBFBBFFRLR\nFBFBBFF\n").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 8: expected 10 seat characters, found end of line"
+        );
+
+        let e = Day05::parse("FBFBBFFRLRL\n").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 11: expected the end of the line, found `L`"
+        );
+    }
+}
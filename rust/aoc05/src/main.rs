@@ -1,161 +1,87 @@
-use std::io::{self, Read};
+use aoc05::{Codec, Seat};
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+const USAGE: &str = "Usage: aoc05 [--rows <n>] [--cols <n>] [--letters <FBLR>] \
+    [--encode <row>,<col>] [--decode <pass>] [--map] < input";
 
-#[derive(Debug)]
-struct TicketDecoder {
-    pub row_low: u8,
-    pub row_high: u8,
-    pub col_low: u8,
-    pub col_high: u8,
+fn parse_count(arg: Option<&String>) -> aoc_core::Result<u32> {
+    Ok(arg.ok_or(USAGE)?.parse()?)
 }
 
-fn midpoint(low: u8, high: u8) -> u8 {
-    (low & high) + ((low ^ high) >> 1)
-}
-
-impl TicketDecoder {
-    fn new() -> TicketDecoder {
-        TicketDecoder {
-            row_low: 0,
-            row_high: 127,
-            col_low: 0,
-            col_high: 7,
-        }
-    }
-
-    fn reset(&mut self) {
-        self.row_low = 0;
-        self.row_high = 127;
-        self.col_low = 0;
-        self.col_high = 7;
-    }
-
-    fn find_seat(&mut self, seq: &str) -> (u8, u8) {
-        for (i, c) in seq.chars().enumerate() {
-            match i {
-                0..=6 => {
-                    let m = midpoint(self.row_low, self.row_high);
-                    match c {
-                        'F' => self.row_high = m,
-                        'B' => self.row_low = m + 1,
-                        _ => unreachable!(),
-                    }
-                }
-                7..=9 => {
-                    let m = midpoint(self.col_low, self.col_high);
-                    match c {
-                        'R' => self.col_low = m + 1,
-                        'L' => self.col_high = m,
-                        _ => unreachable!(),
-                    }
-                }
-                _ => unreachable!(),
-            }
-        }
-        (self.row_low, self.col_low)
+fn parse_letters(arg: Option<&String>) -> aoc_core::Result<[char; 4]> {
+    match arg.ok_or(USAGE)?.chars().collect::<Vec<_>>()[..] {
+        [a, b, c, d] => Ok([a, b, c, d]),
+        _ => Err("--letters takes the row lower/upper then column lower/upper letters".into()),
     }
 }
 
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
+fn parse_seat(arg: Option<&String>) -> aoc_core::Result<Seat> {
+    let (row, col) = arg.and_then(|a| a.split_once(',')).ok_or(USAGE)?;
 
-    let start = std::time::Instant::now();
-    part1(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
-
-    let start = std::time::Instant::now();
-    part2(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
-
-    Ok(())
+    Ok(Seat {
+        row: row.parse()?,
+        col: col.parse()?,
+    })
 }
 
-fn part1(input: &str) -> Result<()> {
-    let mut max_seat_id = 0;
-    let mut seat_decoder = TicketDecoder::new();
+/// With no arguments, solve both parts. Otherwise `--encode` and `--decode`
+/// convert single passes and `--map` draws every seat from the passes on
+/// stdin, on a plane shaped by `--rows`, `--cols` and `--letters`
+fn main() -> aoc_core::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-    for line in input.lines() {
-        seat_decoder.reset();
-        let (r, c) = seat_decoder.find_seat(line);
-        let seat_id = (r as u32) * 8 + (c as u32);
-        if seat_id > max_seat_id {
-            max_seat_id = seat_id;
-        }
+    if args.is_empty() {
+        return aoc_core::main::<aoc05::Day05>();
     }
 
-    println!("part 1 solution: {}", max_seat_id);
-    Ok(())
-}
-
-fn part2(input: &str) -> Result<()> {
-    let mut seat_decoder = TicketDecoder::new();
-
-    let seat_ids: Vec<u32> = input
-        .lines()
-        .map(|line| {
-            seat_decoder.reset();
-            let (r, c) = seat_decoder.find_seat(line);
-            (r as u32) * 8 + (c as u32)
-        })
-        .collect();
-
-    // For a list of N consecutive numbers the sum of 1 to N inclusive
-    // is N * (N + 1 ) / 2. Here we calculate the sum if there were no
-    // missing numbers between 0 and the max seat id and then subtract
-    // the sum of those missing in the front as well as the seat ids that
-    // we observe, leaving the missing seat id.
-
-    let mut seat_min = u32::MAX;
-    let mut seat_max = u32::MIN;
-    let mut s = 0;
-
-    for &x in seat_ids.iter() {
-        if x < seat_min {
-            seat_min = x;
-        }
-        if x > seat_max {
-            seat_max = x;
+    let mut rows = 128;
+    let mut cols = 8;
+    let mut letters = ['F', 'B', 'L', 'R'];
+    let mut encode = None;
+    let mut decode = None;
+    let mut map = false;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--rows" => rows = parse_count(it.next())?,
+            "--cols" => cols = parse_count(it.next())?,
+            "--letters" => letters = parse_letters(it.next())?,
+            "--encode" => encode = Some(parse_seat(it.next())?),
+            "--decode" => decode = Some(it.next().ok_or(USAGE)?.clone()),
+            "--map" => map = true,
+            _ => return Err(USAGE.into()),
         }
-        s += x;
     }
 
-    let total_front = {
-        let x = seat_min - 1;
-        x * (x + 1) / 2
-    };
+    let codec = Codec::new(rows, cols, letters)?;
 
-    let total_back = seat_max * (seat_max + 1) / 2;
+    if let Some(seat) = encode {
+        println!("{}", codec.encode(seat)?);
+    }
 
-    let missing = total_back - total_front - s;
+    if let Some(pass) = decode {
+        let seat = codec.decode(&pass, &pass)?;
+        println!(
+            "row {}, column {}, seat id {}",
+            seat.row,
+            seat.col,
+            codec.seat_id(seat)?
+        );
+    }
 
-    println!("part 2 solution: {}", missing);
+    if map {
+        let input = aoc_core::input::read_stdin()?;
+        let ids = input
+            .lines()
+            .map(|line| codec.seat_id(codec.decode_bits(&input, line)?))
+            .collect::<aoc_core::Result<Vec<_>>>()?;
+
+        let seats = codec.seat_map(&ids);
+        println!("{}", seats);
+        for id in seats.free_between_occupied() {
+            println!("free between two passengers: seat id {}", id);
+        }
+    }
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part1_test() {
-        let mut seat_decoder = TicketDecoder::new();
-
-        let (r, c) = seat_decoder.find_seat("BFFFBBFRRR");
-        assert!(r == 70);
-        assert!(c == 7);
-        seat_decoder.reset();
-
-        let (r, c) = seat_decoder.find_seat("FFFBBBFRRR");
-        assert!(r == 14);
-        assert!(c == 7);
-        seat_decoder.reset();
-
-        let (r, c) = seat_decoder.find_seat("BBFFBBFRLL");
-        assert!(r == 102);
-        assert!(c == 4);
-        seat_decoder.reset();
-    }
-}
@@ -0,0 +1,338 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::{BitAnd, BitOr};
+
+use aoc_core::{ParseError, Result, Solution};
+
+/// The questions, `a` to `z`, one member answered yes to, as a 26-bit set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AnswerSet(u32);
+
+impl AnswerSet {
+    pub const ALL: AnswerSet = AnswerSet((1 << 26) - 1);
+
+    /// Parse one member's answers, a line of `input`
+    pub fn parse(input: &str, line: &str) -> Result<AnswerSet> {
+        let mut set = AnswerSet::default();
+
+        for (i, c) in line.char_indices() {
+            match AnswerSet::bit(c) {
+                Some(bit) => set.0 |= bit,
+                None => {
+                    let found = &line[i..i + c.len_utf8()];
+                    return Err(ParseError::at(input, found, "a question `a` to `z`").into());
+                }
+            }
+        }
+
+        Ok(set)
+    }
+
+    /// The bit for `question`, or `None` if it is not `a` to `z`
+    fn bit(question: char) -> Option<u32> {
+        if question.is_ascii_lowercase() {
+            Some(1 << (question as u32 - 'a' as u32))
+        } else {
+            None
+        }
+    }
+
+    pub fn insert(&mut self, question: char) -> Result<()> {
+        let bit = AnswerSet::bit(question)
+            .ok_or_else(|| format!("`{}` is not a question `a` to `z`", question))?;
+        self.0 |= bit;
+
+        Ok(())
+    }
+
+    /// Whether `question` was answered; never for anything but `a` to `z`
+    pub fn contains(&self, question: char) -> bool {
+        matches!(AnswerSet::bit(question), Some(bit) if self.0 & bit != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = char> + '_ {
+        ('a'..='z').filter(move |&q| self.contains(q))
+    }
+}
+
+impl BitOr for AnswerSet {
+    type Output = AnswerSet;
+
+    fn bitor(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 | other.0)
+    }
+}
+
+impl BitAnd for AnswerSet {
+    type Output = AnswerSet;
+
+    fn bitand(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 & other.0)
+    }
+}
+
+impl fmt::Display for AnswerSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.iter().try_for_each(|q| write!(f, "{}", q))
+    }
+}
+
+/// The answers of each member of one group
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    pub members: Vec<AnswerSet>,
+}
+
+impl Group {
+    /// The questions anyone in the group answered
+    pub fn anyone(&self) -> AnswerSet {
+        self.members
+            .iter()
+            .fold(AnswerSet::default(), |a, &m| a | m)
+    }
+
+    /// The questions everyone in the group answered
+    pub fn everyone(&self) -> AnswerSet {
+        self.members.iter().fold(AnswerSet::ALL, |a, &m| a & m)
+    }
+
+    /// How many members answered each question, `a` first
+    pub fn counts(&self) -> [usize; 26] {
+        let mut counts = [0; 26];
+
+        for m in &self.members {
+            for q in m.iter() {
+                counts[q as usize - 'a' as usize] += 1;
+            }
+        }
+
+        counts
+    }
+
+    fn filter<F: Fn(usize) -> bool>(&self, keep: F) -> AnswerSet {
+        let mut set = AnswerSet::default();
+
+        for (i, &n) in self.counts().iter().enumerate() {
+            if keep(n) {
+                set.0 |= 1 << i;
+            }
+        }
+
+        set
+    }
+
+    /// The questions at least `k` members answered
+    pub fn at_least(&self, k: usize) -> AnswerSet {
+        match k {
+            0 => AnswerSet::ALL,
+            1 => self.anyone(),
+            _ => self.filter(|n| n >= k),
+        }
+    }
+
+    /// The questions exactly `k` members answered
+    pub fn exactly(&self, k: usize) -> AnswerSet {
+        self.filter(|n| n == k)
+    }
+
+    /// The questions more than half the members answered
+    pub fn majority(&self) -> AnswerSet {
+        self.at_least(self.members.len() / 2 + 1)
+    }
+}
+
+/// Groups of answers, one member per line and a blank line between groups
+pub fn parse_groups(input: &str) -> Result<Vec<Group>> {
+    let mut groups = vec![];
+    let mut members = vec![];
+
+    for line in input.lines() {
+        if line.is_empty() {
+            if !members.is_empty() {
+                groups.push(Group { members });
+                members = vec![];
+            }
+        } else {
+            members.push(AnswerSet::parse(input, line)?);
+        }
+    }
+
+    if !members.is_empty() {
+        groups.push(Group { members });
+    }
+
+    Ok(groups)
+}
+
+/// For each question, `a` first, how many groups have it in the set `query`
+/// picks out of them
+pub fn histogram<F: Fn(&Group) -> AnswerSet>(groups: &[Group], query: F) -> [usize; 26] {
+    let mut counts = [0; 26];
+
+    for g in groups {
+        for q in query(g).iter() {
+            counts[q as usize - 'a' as usize] += 1;
+        }
+    }
+
+    counts
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input<'a> = Vec<Group>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Group>> {
+        parse_groups(input)
+    }
+
+    fn part1(groups: &Vec<Group>) -> Result<usize> {
+        Ok(groups.iter().map(|g| g.anyone().len()).sum())
+    }
+
+    fn part2(groups: &Vec<Group>) -> Result<usize> {
+        Ok(groups.iter().map(|g| g.everyone().len()).sum())
+    }
+}
+
+// The original `HashSet` answers, kept to benchmark against `AnswerSet`
+
+pub fn part1(input: &str) -> Result<usize> {
+    let group_answers = input
+        .lines()
+        .fold(vec![HashSet::with_capacity(26)], |mut acc, v| {
+            if v.is_empty() {
+                acc.push(HashSet::with_capacity(26));
+                acc
+            } else {
+                if let Some(hs) = acc.last_mut() {
+                    v.chars().for_each(|g| {
+                        hs.insert(g);
+                    });
+                }
+                acc
+            }
+        });
+    let total: usize = group_answers.iter().map(|g| g.len()).sum();
+    Ok(total)
+}
+
+pub fn part1_v2(input: &str) -> Result<usize> {
+    // Use lookup table pulling that returns True for first time a character is seen, but
+    // no subsequent time so each letter is counted once per group
+    const OFFSET: usize = 'a' as usize;
+    let groups: Vec<Vec<&str>> = input.split("\n\n").map(|g| g.lines().collect()).collect();
+
+    let total: usize = groups
+        .iter()
+        .map(|g| {
+            let mut t = [true; 26];
+            g.iter()
+                .flat_map(|r| r.chars())
+                .filter(|&x| {
+                    let ix = (x as usize) - OFFSET;
+                    let y = t[ix];
+                    t[ix] = false;
+                    y
+                })
+                .count()
+        })
+        .sum();
+
+    Ok(total)
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    let mut gid = 0;
+    let mut hs_cmp = HashSet::with_capacity(26);
+
+    let group_common_answers =
+        input
+            .lines()
+            .fold(vec![HashSet::with_capacity(26)], |mut acc, v| {
+                if v.is_empty() {
+                    gid = 0;
+                    acc.push(HashSet::with_capacity(26));
+                    acc
+                } else {
+                    if gid == 0 {
+                        if let Some(hs) = acc.last_mut() {
+                            v.chars().for_each(|g| {
+                                hs.insert(g);
+                            });
+                        }
+                    } else {
+                        hs_cmp.clear();
+                        v.chars().for_each(|g| {
+                            hs_cmp.insert(g);
+                        });
+
+                        if let Some(hs) = acc.last_mut() {
+                            let x: HashSet<char> = hs.intersection(&hs_cmp).copied().collect();
+                            hs.clear();
+                            hs.extend(&x);
+                        }
+                    }
+
+                    gid += 1;
+                    acc
+                }
+            });
+
+    let total: usize = group_common_answers.iter().map(|g| g.len()).sum();
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_v2_test() {
+        let input =
+            aoc_core::input::read_file(concat!(env!("CARGO_MANIFEST_DIR"), "/input")).unwrap();
+        assert_eq!(part1_v2(&input).unwrap(), part1(&input).unwrap());
+
+        let groups = Day06::parse(&input).unwrap();
+        assert_eq!(Day06::part1(&groups).unwrap(), part1(&input).unwrap());
+        assert_eq!(Day06::part2(&groups).unwrap(), part2(&input).unwrap());
+    }
+
+    #[test]
+    fn quorum_test() {
+        let groups = parse_groups("abc\nab\nad\n\nx\n").unwrap();
+        let g = &groups[0];
+
+        assert_eq!(g.anyone().to_string(), "abcd");
+        assert_eq!(g.everyone().to_string(), "a");
+        assert_eq!(g.at_least(2).to_string(), "ab");
+        assert_eq!(g.exactly(1).to_string(), "cd");
+        assert_eq!(g.majority(), g.at_least(2));
+
+        let counts = histogram(&groups, Group::anyone);
+        assert_eq!((counts[0], counts[23], counts[25]), (1, 1, 0));
+
+        assert_eq!(
+            parse_groups("ab\naB\n").unwrap_err().to_string(),
+            "line 2, column 2: expected a question `a` to `z`, found `B`"
+        );
+
+        let mut set = AnswerSet::default();
+        set.insert('q').unwrap();
+        assert!(set.insert('{').is_err());
+        assert!(set.insert('é').is_err());
+        assert!(set.contains('q') && !set.contains('Q') && !set.contains('\u{10ffff}'));
+        assert_eq!(set.to_string(), "q");
+    }
+}
@@ -0,0 +1,329 @@
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use aoc_core::{ParseError, Result};
+
+use crate::Bag;
+
+/// Which way to follow the containment edges
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    /// Towards the bags that hold this one
+    Out,
+    /// Towards the bags this one holds
+    In,
+}
+
+/// The bag rules as a graph, with an edge from each bag to the bags it
+/// directly contains. Colours only ever mentioned as contents are nodes too.
+#[derive(Debug)]
+pub struct BagGraph<'a> {
+    colors: Vec<&'a str>,
+    index: HashMap<&'a str, usize>,
+    /// The bags each bag holds, and how many of each
+    contents: Vec<Vec<(usize, u64)>>,
+    /// The bags each bag is directly held by
+    containers: Vec<Vec<usize>>,
+    ancestors: Vec<OnceCell<Vec<usize>>>,
+    descendants: Vec<OnceCell<Vec<usize>>>,
+    totals: Vec<OnceCell<u64>>,
+}
+
+impl<'a> BagGraph<'a> {
+    /// Build the graph from one rule per line of `input`
+    pub fn parse(input: &'a str) -> Result<BagGraph<'a>> {
+        let mut graph = BagGraph {
+            colors: vec![],
+            index: HashMap::new(),
+            contents: vec![],
+            containers: vec![],
+            ancestors: vec![],
+            descendants: vec![],
+            totals: vec![],
+        };
+        let mut has_rule = vec![];
+
+        for line in input.lines() {
+            let bag = Bag::from_line(input, line)?;
+            let n = graph.node(bag.color);
+
+            has_rule.resize(graph.colors.len(), false);
+            if has_rule[n] {
+                let expected = "a colour that does not already have a rule";
+                return Err(ParseError::at(input, bag.color, expected).into());
+            }
+            has_rule[n] = true;
+
+            for rule in &bag.rules {
+                let m = graph.node(rule.color);
+                graph.contents[n].push((m, rule.count as u64));
+                graph.containers[m].push(n);
+            }
+        }
+
+        Ok(graph)
+    }
+
+    fn node(&mut self, color: &'a str) -> usize {
+        if let Some(&n) = self.index.get(color) {
+            return n;
+        }
+
+        let n = self.colors.len();
+        self.colors.push(color);
+        self.index.insert(color, n);
+        self.contents.push(vec![]);
+        self.containers.push(vec![]);
+        self.ancestors.push(OnceCell::new());
+        self.descendants.push(OnceCell::new());
+        self.totals.push(OnceCell::new());
+
+        n
+    }
+
+    fn find(&self, color: &str) -> Result<usize> {
+        self.index
+            .get(color)
+            .copied()
+            .ok_or_else(|| format!("there is no `{}` bag", color).into())
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    fn neighbours(&self, n: usize, dir: Direction) -> Vec<usize> {
+        match dir {
+            Direction::Out => self.containers[n].clone(),
+            Direction::In => self.contents[n].iter().map(|&(m, _)| m).collect(),
+        }
+    }
+
+    /// Every node reachable from `n`, not counting `n` itself unless it is on a
+    /// cycle. Each answer is kept, and reused when a later search reaches `n`.
+    fn closure(&self, n: usize, dir: Direction) -> &[usize] {
+        let memo = match dir {
+            Direction::Out => &self.ancestors,
+            Direction::In => &self.descendants,
+        };
+
+        if let Some(found) = memo[n].get() {
+            return found;
+        }
+
+        let mut seen = vec![false; self.len()];
+        let mut stack = self.neighbours(n, dir);
+
+        while let Some(m) = stack.pop() {
+            if seen[m] {
+                continue;
+            }
+            seen[m] = true;
+
+            match memo[m].get() {
+                Some(known) => {
+                    for &k in known {
+                        seen[k] = true;
+                    }
+                }
+                None => stack.extend(self.neighbours(m, dir)),
+            }
+        }
+
+        let found = (0..self.len()).filter(|&m| seen[m]).collect();
+        memo[n].get_or_init(|| found)
+    }
+
+    fn colors_of(&self, nodes: &[usize]) -> Vec<&'a str> {
+        let mut colors: Vec<_> = nodes.iter().map(|&n| self.colors[n]).collect();
+        colors.sort_unstable();
+        colors
+    }
+
+    /// The bags that eventually hold a `color` bag, in alphabetical order
+    pub fn ancestors(&self, color: &str) -> Result<Vec<&'a str>> {
+        Ok(self.colors_of(self.closure(self.find(color)?, Direction::Out)))
+    }
+
+    /// The bags a `color` bag eventually holds, in alphabetical order
+    pub fn descendants(&self, color: &str) -> Result<Vec<&'a str>> {
+        Ok(self.colors_of(self.closure(self.find(color)?, Direction::In)))
+    }
+
+    fn cycle_error(&self, path: &[usize], n: usize) -> String {
+        let start = path.iter().position(|&m| m == n).unwrap_or(0);
+        let cycle: Vec<_> = path[start..]
+            .iter()
+            .chain(std::iter::once(&n))
+            .map(|&m| self.colors[m])
+            .collect();
+
+        format!("bags contain themselves: {}", cycle.join(" -> "))
+    }
+
+    fn total(&self, n: usize, path: &mut Vec<usize>) -> Result<u64> {
+        if let Some(&t) = self.totals[n].get() {
+            return Ok(t);
+        }
+        if path.contains(&n) {
+            return Err(self.cycle_error(path, n).into());
+        }
+
+        path.push(n);
+        let mut total: u64 = 0;
+        for &(m, count) in &self.contents[n] {
+            let inner = self.total(m, path)?;
+            total = inner
+                .checked_add(1)
+                .and_then(|bags| bags.checked_mul(count))
+                .and_then(|bags| bags.checked_add(total))
+                .ok_or_else(|| {
+                    format!("a `{}` bag holds too many bags to count", self.colors[n])
+                })?;
+        }
+        path.pop();
+
+        Ok(*self.totals[n].get_or_init(|| total))
+    }
+
+    /// How many bags a `color` bag holds in all, at every depth. Fails if the
+    /// bags it holds contain themselves, or the count overflows.
+    pub fn total_contents(&self, color: &str) -> Result<u64> {
+        self.total(self.find(color)?, &mut vec![])
+    }
+
+    /// Every bag, each before all the bags it holds, or an error naming a
+    /// cycle if there is no such order
+    pub fn topo_order(&self) -> Result<Vec<&'a str>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            New,
+            Open,
+            Done,
+        }
+
+        fn visit(
+            graph: &BagGraph,
+            n: usize,
+            marks: &mut [Mark],
+            path: &mut Vec<usize>,
+            order: &mut Vec<usize>,
+        ) -> Result<()> {
+            match marks[n] {
+                Mark::Done => return Ok(()),
+                Mark::Open => return Err(graph.cycle_error(path, n).into()),
+                Mark::New => (),
+            }
+
+            marks[n] = Mark::Open;
+            path.push(n);
+            for &(m, _) in &graph.contents[n] {
+                visit(graph, m, marks, path, order)?;
+            }
+            path.pop();
+            marks[n] = Mark::Done;
+            order.push(n);
+
+            Ok(())
+        }
+
+        let mut marks = vec![Mark::New; self.len()];
+        let mut order = Vec::with_capacity(self.len());
+
+        for n in 0..self.len() {
+            visit(self, n, &mut marks, &mut vec![], &mut order)?;
+        }
+
+        Ok(order.iter().rev().map(|&n| self.colors[n]).collect())
+    }
+
+    /// A Graphviz digraph of a `color` bag and everything it holds, with each
+    /// edge labelled by its count
+    pub fn to_dot(&self, color: &str) -> Result<String> {
+        let root = self.find(color)?;
+        let mut nodes = vec![root];
+        nodes.extend(
+            self.closure(root, Direction::In)
+                .iter()
+                .filter(|&&n| n != root),
+        );
+
+        let mut dot = String::from("digraph bags {\n");
+        for &n in &nodes {
+            writeln!(dot, "    \"{}\";", self.colors[n])?;
+        }
+        for &n in &nodes {
+            for &(m, count) in &self.contents[n] {
+                writeln!(
+                    dot,
+                    "    \"{}\" -> \"{}\" [label={}];",
+                    self.colors[n], self.colors[m], count
+                )?;
+            }
+        }
+        dot.push('}');
+
+        Ok(dot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_test() {
+        let input = "\
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+muted yellow bags contain 3 bright white bags.
+bright white bags contain 1 faded blue bag.
+faded blue bags contain no other bags.
+";
+        let graph = BagGraph::parse(input).unwrap();
+
+        assert_eq!(
+            graph.ancestors("bright white").unwrap(),
+            ["light red", "muted yellow"]
+        );
+        assert_eq!(
+            graph.descendants("muted yellow").unwrap(),
+            ["bright white", "faded blue"]
+        );
+        assert_eq!(graph.ancestors("faded blue").unwrap().len(), 3);
+        assert_eq!(
+            graph.total_contents("light red").unwrap(),
+            1 + 1 + 2 * (1 + 3 * 2)
+        );
+        assert_eq!(
+            graph.topo_order().unwrap(),
+            ["light red", "muted yellow", "bright white", "faded blue"]
+        );
+        assert!(graph
+            .to_dot("muted yellow")
+            .unwrap()
+            .contains("\"muted yellow\" -> \"bright white\" [label=3];"));
+        assert!(graph.total_contents("dull green").is_err());
+
+        let cyclic = BagGraph::parse(
+            "a b bags contain 1 c d bag.\nc d bags contain 2 a b bags.\ne f bags contain 1 a b bag.\n",
+        )
+        .unwrap();
+        assert_eq!(cyclic.ancestors("a b").unwrap(), ["a b", "c d", "e f"]);
+        assert_eq!(
+            cyclic.total_contents("e f").unwrap_err().to_string(),
+            "bags contain themselves: a b -> c d -> a b"
+        );
+        assert!(cyclic.topo_order().is_err());
+
+        let e = BagGraph::parse("a b bags contain no other bags.\na b bags contain 1 c d bag.\n")
+            .unwrap_err();
+        assert!(e
+            .to_string()
+            .starts_with("line 2, column 1: expected a colour"));
+    }
+}
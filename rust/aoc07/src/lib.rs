@@ -0,0 +1,72 @@
+use aoc_core::parse::{parse_at, split_once_at};
+use aoc_core::{ParseError, Result, Solution};
+
+pub use crate::graph::BagGraph;
+
+pub mod graph;
+
+#[derive(Debug)]
+pub struct Bag<'a> {
+    pub color: &'a str,
+    pub rules: Vec<BagRule<'a>>,
+}
+
+#[derive(Debug)]
+pub struct BagRule<'a> {
+    pub color: &'a str,
+    pub count: u16,
+}
+
+impl<'a> Bag<'a> {
+    fn from_line(input: &str, line: &'a str) -> Result<Bag<'a>> {
+        let (color, rules_sec) = split_once_at(input, line, " bags contain ")?;
+
+        if rules_sec.starts_with("no other bags") {
+            return Ok(Bag {
+                color,
+                rules: Vec::new(),
+            });
+        }
+
+        let rules_sec = rules_sec
+            .strip_suffix('.')
+            .ok_or_else(|| ParseError::at(input, &rules_sec[rules_sec.len()..], "`.`"))?;
+
+        let rules = rules_sec
+            .split(", ")
+            .map(|g| {
+                let (num, rest) = split_once_at(input, g, " ")?;
+                let num = parse_at(input, num, "a bag count")?;
+
+                let (c, _) = split_once_at(input, rest, " bag")?;
+
+                Ok(BagRule {
+                    color: c,
+                    count: num,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Bag { color, rules })
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input<'a> = BagGraph<'a>;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<BagGraph<'_>> {
+        BagGraph::parse(input)
+    }
+
+    fn part1(graph: &BagGraph<'_>) -> Result<usize> {
+        Ok(graph.ancestors("shiny gold")?.len())
+    }
+
+    fn part2(graph: &BagGraph<'_>) -> Result<u64> {
+        graph.total_contents("shiny gold")
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+
+use aoc_core::Result;
+
+use crate::isa::{Flow, Ops};
+
+/// Where control goes after instruction `i` of a program of `len`: `Some(len)`
+/// is the end of the program, and `None` a jump anywhere else outside it.
+/// Fails for instructions that may or may not jump.
+pub fn successor(op: &Ops, i: usize, len: usize) -> Result<Option<usize>> {
+    let next = match op.def.flow {
+        Flow::Next => i as isize + 1,
+        Flow::Jump(k) => {
+            let offset = op
+                .offset(k)
+                .ok_or_else(|| format!("{}: `{}` has no fixed target", i, op))?;
+            i as isize + offset as isize
+        }
+        Flow::Branch(_) => {
+            return Err(format!("{}: `{}` may or may not jump", i, op).into());
+        }
+    };
+
+    if (0..=len as isize).contains(&next) {
+        Ok(Some(next as usize))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Swapping a `jmp` for a `nop` or back that makes the program terminate
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repair {
+    pub index: usize,
+    pub from: Ops,
+    pub to: Ops,
+}
+
+impl fmt::Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.index, self.from, self.to)
+    }
+}
+
+/// The control-flow graph of a boot program. Every instruction has at most one
+/// successor, so each question below is a single pass over the program;
+/// programs with conditional jumps cannot be analysed.
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    program: Vec<Ops>,
+    successors: Vec<Option<usize>>,
+    /// Whether the end of the program can be reached from each instruction
+    terminating: Vec<bool>,
+    /// The instructions run from the start, in order, until the program ends,
+    /// leaves its bounds or repeats itself
+    path: Vec<usize>,
+}
+
+impl Analysis {
+    pub fn new(program: &[Ops]) -> Result<Analysis> {
+        let len = program.len();
+        let successors = program
+            .iter()
+            .enumerate()
+            .map(|(i, op)| successor(op, i, len))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Walk the edges backwards from the end
+        let mut predecessors = vec![vec![]; len + 1];
+        for (i, s) in successors.iter().enumerate() {
+            if let Some(s) = *s {
+                predecessors[s].push(i);
+            }
+        }
+
+        let mut terminating = vec![false; len + 1];
+        terminating[len] = true;
+        let mut stack = vec![len];
+        while let Some(n) = stack.pop() {
+            for &p in &predecessors[n] {
+                if !terminating[p] {
+                    terminating[p] = true;
+                    stack.push(p);
+                }
+            }
+        }
+        terminating.truncate(len);
+
+        let mut path = vec![];
+        let mut seen = vec![false; len];
+        let mut ip = Some(0);
+        while let Some(i) = ip.filter(|&i| i < len && !seen[i]) {
+            seen[i] = true;
+            path.push(i);
+            ip = successors[i];
+        }
+
+        Ok(Analysis {
+            program: program.to_vec(),
+            successors,
+            terminating,
+            path,
+        })
+    }
+
+    /// Whether the program as it stands runs off its end
+    pub fn terminates(&self) -> bool {
+        self.program.is_empty() || self.terminating[0]
+    }
+
+    /// Whether the end of the program can be reached from instruction `i`
+    pub fn can_terminate(&self, i: usize) -> bool {
+        self.terminating
+            .get(i)
+            .copied()
+            .unwrap_or(i == self.program.len())
+    }
+
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+
+    /// Every single swap of a `jmp` and a `nop` that makes a program that
+    /// does not terminate do so, in the order they would run. Only
+    /// instructions on the path can matter, and a swap works exactly when it
+    /// sends control somewhere that already reaches the end.
+    pub fn repairs(&self) -> Vec<Repair> {
+        if self.terminates() {
+            return vec![];
+        }
+
+        let len = self.program.len();
+
+        self.path
+            .iter()
+            .filter_map(|&i| {
+                let from = &self.program[i];
+                let to = from.flipped()?;
+
+                successor(&to, i, len)
+                    .ok()
+                    .flatten()
+                    .filter(|&s| self.can_terminate(s))
+                    .map(|_| Repair {
+                        index: i,
+                        from: from.clone(),
+                        to,
+                    })
+            })
+            .collect()
+    }
+
+    /// The instructions that never run
+    pub fn dead_code(&self) -> Vec<usize> {
+        let mut live = vec![false; self.program.len()];
+        for &i in &self.path {
+            live[i] = true;
+        }
+
+        (0..self.program.len()).filter(|&i| !live[i]).collect()
+    }
+
+    /// Every loop in the program, reachable or not, each in the order it runs
+    /// starting from its lowest instruction
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let len = self.program.len();
+        // The walk each instruction was first reached by
+        let mut walk_of = vec![None; len];
+        let mut cycles = vec![];
+
+        for start in 0..len {
+            let mut walk = vec![];
+            let mut ip = Some(start);
+
+            while let Some(i) = ip.filter(|&i| i < len) {
+                match walk_of[i] {
+                    None => {
+                        walk_of[i] = Some(start);
+                        walk.push(i);
+                        ip = self.successors[i];
+                    }
+                    Some(w) if w == start => {
+                        let from = walk.iter().position(|&j| j == i).unwrap_or(0);
+                        let mut cycle = walk[from..].to_vec();
+                        let lowest = (0..cycle.len()).min_by_key(|&k| cycle[k]).unwrap_or(0);
+                        cycle.rotate_left(lowest);
+                        cycles.push(cycle);
+                        break;
+                    }
+                    Some(_) => break,
+                }
+            }
+        }
+
+        cycles.sort();
+        cycles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analysis_test() {
+        let input = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+        let a = Analysis::new(&crate::parse_input(input).unwrap()).unwrap();
+
+        assert!(!a.terminates());
+        assert_eq!(a.path(), [0, 1, 2, 6, 7, 3, 4]);
+        assert_eq!(
+            a.repairs(),
+            [Repair {
+                index: 7,
+                from: Ops::jmp(-4),
+                to: Ops::nop(-4),
+            }]
+        );
+        assert_eq!(a.dead_code(), [5, 8]);
+        assert_eq!(a.cycles(), [vec![1, 2, 6, 7, 3, 4]]);
+        assert!(a.can_terminate(8) && a.can_terminate(9) && !a.can_terminate(5));
+
+        let input = "jmp +3\njmp +0\nacc +1\njmp -1\n";
+        let a = Analysis::new(&crate::parse_input(input).unwrap()).unwrap();
+        assert_eq!(a.cycles(), [vec![1], vec![2, 3]]);
+        assert_eq!(a.dead_code(), [1]);
+        assert_eq!(a.repairs().len(), 1);
+        assert_eq!(a.repairs()[0].to_string(), "3: jmp -1 -> nop -1");
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+use aoc_core::{ParseError, Result};
+
+use crate::isa::{InstructionSet, Kind, Operand, Ops};
+
+/// What the source said around one instruction, or around the end of the
+/// program for the last one
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Annotations {
+    labels: Vec<String>,
+    /// Comment lines before the instruction
+    comments: Vec<String>,
+    /// The comment after the instruction on its own line
+    note: Option<String>,
+}
+
+/// A boot program with the labels and comments of its source. It prints as
+/// source that assembles back to the same program; without labels or
+/// comments that is just one instruction per line, as in the puzzle input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub ops: Vec<Ops>,
+    /// One per instruction, plus one for the end of the program
+    annotations: Vec<Annotations>,
+}
+
+/// Split off a `#` or `;` comment
+fn split_comment(line: &str) -> (&str, Option<&str>) {
+    match line.find(['#', ';']) {
+        Some(i) => (&line[..i], Some(line[i + 1..].trim())),
+        None => (line, None),
+    }
+}
+
+/// Split off a leading `name:` label
+fn split_label(code: &str) -> (Option<&str>, &str) {
+    let code = code.trim();
+
+    match code.split_once(':') {
+        Some((name, rest))
+            if !name.is_empty()
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && !name.starts_with(|c: char| c.is_ascii_digit()) =>
+        {
+            (Some(name), rest.trim())
+        }
+        _ => (None, code),
+    }
+}
+
+impl Program {
+    /// A program with no labels or comments
+    pub fn from_ops(ops: Vec<Ops>) -> Program {
+        Program {
+            annotations: vec![Annotations::default(); ops.len() + 1],
+            ops,
+        }
+    }
+
+    /// Assemble one instruction per line, using opcodes from `set`. A line
+    /// may start with a `name:` label, which jumps can name instead of an
+    /// offset, and anything after `#` or `;` is a comment.
+    pub fn parse(input: &str, set: &InstructionSet) -> Result<Program> {
+        // Find every label first so jumps can refer forwards
+        let mut targets: HashMap<&str, usize> = HashMap::new();
+        let mut count = 0;
+
+        for line in input.lines() {
+            let (label, code) = split_label(split_comment(line).0);
+
+            if let Some(name) = label {
+                if targets.insert(name, count).is_some() {
+                    return Err(ParseError::at(input, name, "a label not used already").into());
+                }
+            }
+            if !code.is_empty() {
+                count += 1;
+            }
+        }
+
+        let mut ops = Vec::with_capacity(count);
+        let mut annotations = vec![Annotations::default(); count + 1];
+
+        for line in input.lines() {
+            let (code, comment) = split_comment(line);
+            let (label, code) = split_label(code);
+            let here = &mut annotations[ops.len()];
+
+            if let Some(name) = label {
+                here.labels.push(name.to_string());
+            }
+
+            if code.is_empty() {
+                here.comments.extend(comment.map(str::to_string));
+                continue;
+            }
+
+            here.note = comment.map(str::to_string);
+            let i = ops.len() as i32;
+            ops.push(set.parse_with(input, code, |name| targets.get(name).map(|&t| t as i32 - i))?);
+        }
+
+        Ok(Program { ops, annotations })
+    }
+
+    /// Name every jump target that has no label yet `L<index>`, so the
+    /// program prints with labels rather than offsets
+    pub fn label_jumps(&mut self) {
+        for i in 0..self.ops.len() {
+            for (k, kind) in self.ops[i].def.operands.iter().enumerate() {
+                let target = match (kind, self.ops[i].offset(k)) {
+                    (Kind::Target, Some(offset)) => i as isize + offset as isize,
+                    _ => continue,
+                };
+
+                if let Some(a) = self.annotation_mut(target) {
+                    if a.labels.is_empty() {
+                        a.labels.push(format!("L{}", target));
+                    }
+                }
+            }
+        }
+    }
+
+    fn annotation(&self, target: isize) -> Option<&Annotations> {
+        usize::try_from(target)
+            .ok()
+            .and_then(|t| self.annotations.get(t))
+    }
+
+    fn annotation_mut(&mut self, target: isize) -> Option<&mut Annotations> {
+        usize::try_from(target)
+            .ok()
+            .and_then(move |t| self.annotations.get_mut(t))
+    }
+
+    /// Instruction `i`, with jumps to labelled instructions naming the label
+    fn source(&self, i: usize) -> String {
+        let op = &self.ops[i];
+        let mut out = op.name().to_string();
+
+        for (k, (kind, arg)) in op.def.operands.iter().zip(&op.args).enumerate() {
+            out.push_str(if k == 0 { " " } else { ", " });
+
+            let label = match (kind, arg) {
+                (Kind::Target, Operand::Imm(offset)) => self
+                    .annotation(i as isize + *offset as isize)
+                    .and_then(|a| a.labels.first()),
+                _ => None,
+            };
+
+            match label {
+                Some(name) => out.push_str(name),
+                None => out.push_str(&arg.to_string()),
+            }
+        }
+
+        out
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indent = if self.annotations.iter().any(|a| !a.labels.is_empty()) {
+            "    "
+        } else {
+            ""
+        };
+
+        for (i, a) in self.annotations.iter().enumerate() {
+            for c in &a.comments {
+                writeln!(f, "# {}", c)?;
+            }
+            for l in &a.labels {
+                writeln!(f, "{}:", l)?;
+            }
+
+            if i < self.ops.len() {
+                match &a.note {
+                    Some(note) => writeln!(f, "{}{:<14} # {}", indent, self.source(i), note)?,
+                    None => writeln!(f, "{}{}", indent, self.source(i))?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let set = InstructionSet::handheld();
+        let source = "\
+# count up
+start:
+    nop +0
+loop:
+    acc +1         # one more
+    jmp end
+    jmp loop
+end:
+";
+        let program = Program::parse(source, &set).unwrap();
+        assert_eq!(
+            program.ops,
+            [Ops::nop(0), Ops::acc(1), Ops::jmp(2), Ops::jmp(-2)]
+        );
+        assert_eq!(program.to_string(), source);
+        assert_eq!(Program::parse(&program.to_string(), &set).unwrap(), program);
+
+        let plain = "nop +0\nacc +1\njmp +2\njmp -2\n";
+        let mut program = Program::parse(plain, &set).unwrap();
+        assert_eq!(program.to_string(), plain);
+
+        program.label_jumps();
+        assert_eq!(
+            program.to_string(),
+            "    nop +0\nL1:\n    acc +1\n    jmp L4\n    jmp L1\nL4:\n"
+        );
+
+        let e = Program::parse("nop +0\n\nmul +2\n", &set).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 3, column 1: expected `acc`, `jmp` or `nop`, found `mul`"
+        );
+        let e = Program::parse("jmp nowhere\n", &set).unwrap_err();
+        assert!(e
+            .to_string()
+            .ends_with("expected a signed offset or a label, found `nowhere`"));
+    }
+}
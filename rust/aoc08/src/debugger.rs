@@ -0,0 +1,250 @@
+use aoc_core::parse::parse_at;
+use aoc_core::Result;
+
+use crate::{Breakpoint, InstructionSet, Machine, Status};
+
+pub const HELP: &str = "\
+step [n]                 run the next n instructions (1 by default), ignoring breakpoints
+continue                 run until a breakpoint, a loop or the end of the program
+break <i> | acc <v>      stop before instruction i, or when acc becomes v
+delete <i> | acc <v>     remove a breakpoint
+breakpoints              list the breakpoints
+print acc|ip|steps|output|trace|<register>
+                         show the machine's state, or every instruction traced
+trace on|off             start or stop tracing
+patch <i> <op> <arg>     replace instruction i, e.g. `patch 42 nop +0`
+list [<from> [<to>]]     show the program around the next instruction, or a range
+reset                    start the program again
+quit                     leave the debugger";
+
+/// A `Machine` driven by text commands, for poking at a boot program by hand
+#[derive(Debug)]
+pub struct Debugger {
+    pub machine: Machine,
+    /// What `patch` may write
+    instructions: InstructionSet,
+}
+
+impl Debugger {
+    pub fn new(machine: Machine) -> Debugger {
+        Debugger::with_instructions(machine, InstructionSet::handheld())
+    }
+
+    pub fn with_instructions(machine: Machine, instructions: InstructionSet) -> Debugger {
+        Debugger {
+            machine,
+            instructions,
+        }
+    }
+
+    /// Where the machine is and what it will do next
+    pub fn position(&self) -> String {
+        let m = &self.machine;
+
+        match m.status() {
+            Status::Running => format!(
+                "next {}: {}, acc {}",
+                m.ip(),
+                m.program()[m.ip() as usize],
+                m.acc()
+            ),
+            Status::Faulted => format!(
+                "{} at {}: {}, acc {}",
+                Status::Faulted,
+                m.ip(),
+                m.fault().unwrap_or_default(),
+                m.acc()
+            ),
+            status => format!("{} at {}, acc {}", status, m.ip(), m.acc()),
+        }
+    }
+
+    fn breakpoint(args: &[&str]) -> Result<Breakpoint> {
+        match args {
+            [i] => Ok(Breakpoint::At(i.parse()?)),
+            ["acc", v] => Ok(Breakpoint::Acc(v.parse()?)),
+            _ => Err("expected an instruction index or `acc <value>`".into()),
+        }
+    }
+
+    fn list(&self, from: usize, to: usize) -> String {
+        let m = &self.machine;
+        let to = to.min(m.program().len());
+        let from = from.min(to);
+
+        (from..to)
+            .map(|i| {
+                let marker = if m.ip() == i as isize { "=>" } else { "  " };
+                let brk = if m.breakpoints().contains(&Breakpoint::At(i)) {
+                    '*'
+                } else {
+                    ' '
+                };
+                format!("{}{}{:>5}: {}", marker, brk, i, m.program()[i])
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Run one command line, returning what to show for it, or `None` to quit
+    pub fn execute(&mut self, line: &str) -> Result<Option<String>> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (command, args) = match words.split_first() {
+            Some((c, args)) => (*c, args),
+            None => return Ok(Some(String::new())),
+        };
+
+        let out = match (command, args) {
+            ("step" | "s", _) => {
+                let n = match args {
+                    [] => 1,
+                    [n] => n.parse()?,
+                    _ => return Err("expected `step [n]`".into()),
+                };
+                for _ in 0..n {
+                    if self.machine.step() != Status::Running {
+                        break;
+                    }
+                }
+                self.position()
+            }
+            ("continue" | "c", []) => {
+                self.machine.run();
+                self.position()
+            }
+            ("break" | "b", _) => {
+                let b = Debugger::breakpoint(args)?;
+                let len = self.machine.program().len();
+                if let Breakpoint::At(i) = b {
+                    if i >= len {
+                        return Err(format!(
+                            "there is no instruction {} in a program of {}",
+                            i, len
+                        )
+                        .into());
+                    }
+                }
+                self.machine.add_breakpoint(b);
+                format!("breakpoint {}", b)
+            }
+            ("delete" | "d", _) => {
+                let b = Debugger::breakpoint(args)?;
+                if !self.machine.remove_breakpoint(b) {
+                    return Err(format!("there is no breakpoint {}", b).into());
+                }
+                format!("deleted breakpoint {}", b)
+            }
+            ("breakpoints", []) => self
+                .machine
+                .breakpoints()
+                .iter()
+                .map(|b| b.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ("print" | "p", [what]) => match *what {
+                "acc" => self.machine.acc().to_string(),
+                "ip" => self.machine.ip().to_string(),
+                "steps" => self.machine.steps().to_string(),
+                "output" => format!("{:?}", self.machine.cpu().output),
+                "trace" => self
+                    .machine
+                    .trace()
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                _ if what.len() == 1 && what.as_bytes()[0].is_ascii_lowercase() => {
+                    let r = what.as_bytes()[0] - b'a';
+                    self.machine.cpu().registers[r as usize].to_string()
+                }
+                _ => return Err(format!("cannot print `{}`", what).into()),
+            },
+            ("trace", ["on"]) => {
+                self.machine.set_tracing(true);
+                "tracing".to_string()
+            }
+            ("trace", ["off"]) => {
+                self.machine.set_tracing(false);
+                "not tracing".to_string()
+            }
+            ("patch", [i, _, ..]) => {
+                let i: usize = i.parse()?;
+                let op = args[1..].join(" ");
+                let op = self.instructions.parse(&op, &op)?;
+                let out = format!("{:>5}: {}", i, op);
+                self.machine.patch(i, op)?;
+                out
+            }
+            ("list" | "l", _) => {
+                let ip = self.machine.ip().max(0) as usize;
+                let (from, to) = match args {
+                    [] => (ip.saturating_sub(5), ip + 6),
+                    [from] => {
+                        let from: usize = parse_at(from, from, "an instruction index")?;
+                        (from, from.saturating_add(11))
+                    }
+                    [from, to] => (
+                        parse_at(from, from, "an instruction index")?,
+                        parse_at::<usize>(to, to, "an instruction index")?.saturating_add(1),
+                    ),
+                    _ => return Err("expected `list [<from> [<to>]]`".into()),
+                };
+                self.list(from, to)
+            }
+            ("reset", []) => {
+                self.machine.reset();
+                self.position()
+            }
+            ("help" | "h", []) => HELP.to_string(),
+            ("quit" | "q", []) => return Ok(None),
+            _ => return Err(format!("unknown command `{}`; try `help`", line.trim()).into()),
+        };
+
+        Ok(Some(out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(debugger: &mut Debugger, line: &str) -> String {
+        debugger.execute(line).unwrap().unwrap()
+    }
+
+    #[test]
+    fn debugger_test() {
+        let input = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+        let mut d = Debugger::new(Machine::new(
+            crate::parse_input(input).unwrap(),
+            &InstructionSet::handheld(),
+        ));
+
+        assert_eq!(run(&mut d, "step 2"), "next 2: jmp +4, acc 1");
+        assert_eq!(run(&mut d, "break 3"), "breakpoint at 3");
+        assert!(d.execute("break 9").is_err());
+        assert!(d.execute("break 18446744073709551615").is_err());
+
+        assert_eq!(run(&mut d, "continue"), "next 3: acc +3, acc 2");
+        assert_eq!(run(&mut d, "print acc"), "2");
+        assert_eq!(run(&mut d, "print steps"), "5");
+        assert!(d.execute("print x y").is_err());
+
+        assert_eq!(
+            run(&mut d, "list 2 4"),
+            "       2: jmp +4\n=>*    3: acc +3\n       4: jmp -3"
+        );
+        assert_eq!(run(&mut d, "list 7").lines().count(), 2);
+        assert_eq!(run(&mut d, "list 18446744073709551615"), "");
+        assert_eq!(
+            run(&mut d, "list 0 18446744073709551615").lines().count(),
+            9
+        );
+
+        assert_eq!(
+            run(&mut d, "continue"),
+            "about to repeat an instruction at 1, acc 5"
+        );
+        assert_eq!(d.execute("quit").unwrap(), None);
+    }
+}
@@ -0,0 +1,420 @@
+use std::fmt;
+
+use aoc_core::parse::parse_at;
+use aoc_core::{ParseError, Result};
+
+use crate::machine::LoopDetector;
+
+/// An instruction argument
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operand {
+    Imm(i32),
+    /// One of the registers `a` to `z`, by index
+    Reg(u8),
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Imm(x) => write!(f, "{:+}", x),
+            Operand::Reg(r) if *r < 26 => write!(f, "{}", (b'a' + r) as char),
+            Operand::Reg(r) => write!(f, "r{}", r),
+        }
+    }
+}
+
+/// What an opcode accepts in each operand position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Imm,
+    Reg,
+    /// A number or a register
+    Value,
+    /// An offset to jump by, which the assembler also accepts as a label
+    Target,
+}
+
+impl Kind {
+    fn expected(&self) -> &'static str {
+        match self {
+            Kind::Imm => "a signed number",
+            Kind::Reg => "a register `a` to `z`",
+            Kind::Value => "a signed number or a register",
+            Kind::Target => "a signed offset or a label",
+        }
+    }
+}
+
+/// Where control can go after an instruction, as far as static analysis is
+/// concerned
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Flow {
+    /// Always the next instruction
+    Next,
+    /// Always by the offset in the given operand
+    Jump(usize),
+    /// Either the next instruction or by the offset in the given operand
+    Branch(usize),
+}
+
+/// Everything an instruction can change
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cpu {
+    pub acc: i32,
+    pub registers: [i32; 26],
+    /// Whatever `out`-style instructions have sent
+    pub output: Vec<i32>,
+}
+
+impl Cpu {
+    pub fn value(&self, operand: Operand) -> Result<i32> {
+        match operand {
+            Operand::Imm(x) => Ok(x),
+            Operand::Reg(r) => self
+                .registers
+                .get(r as usize)
+                .copied()
+                .ok_or_else(|| format!("there is no register {}", operand).into()),
+        }
+    }
+
+    /// The register `operand` names. The assembler only accepts registers
+    /// for `Kind::Reg` operands, but hand-built instructions may not.
+    pub fn register_mut(&mut self, operand: Operand) -> Result<&mut i32> {
+        match operand {
+            Operand::Reg(r) => self
+                .registers
+                .get_mut(r as usize)
+                .ok_or_else(|| format!("there is no register {}", operand).into()),
+            Operand::Imm(_) => Err(format!("{} is not a register", operand).into()),
+        }
+    }
+}
+
+/// Run an instruction with the given operands, returning the offset to the
+/// next one, 1 to carry on
+pub type Handler = fn(&mut Cpu, &[Operand]) -> Result<isize>;
+
+/// An opcode: its mnemonic, operands, control flow and what it does
+#[derive(Debug, Clone, Copy)]
+pub struct OpDef {
+    pub name: &'static str,
+    pub operands: &'static [Kind],
+    pub flow: Flow,
+    pub exec: Handler,
+}
+
+pub const ACC: OpDef = OpDef {
+    name: "acc",
+    operands: &[Kind::Imm],
+    flow: Flow::Next,
+    exec: |cpu, args| {
+        cpu.acc = cpu
+            .acc
+            .checked_add(cpu.value(args[0])?)
+            .ok_or("the accumulator overflows")?;
+        Ok(1)
+    },
+};
+
+pub const JMP: OpDef = OpDef {
+    name: "jmp",
+    operands: &[Kind::Target],
+    flow: Flow::Jump(0),
+    exec: |cpu, args| Ok(cpu.value(args[0])? as isize),
+};
+
+pub const NOP: OpDef = OpDef {
+    name: "nop",
+    operands: &[Kind::Imm],
+    flow: Flow::Next,
+    exec: |_, _| Ok(1),
+};
+
+/// One instruction of a program
+#[derive(Debug, Clone)]
+pub struct Ops {
+    pub def: OpDef,
+    pub args: Vec<Operand>,
+}
+
+impl Ops {
+    pub fn new(def: OpDef, args: Vec<Operand>) -> Ops {
+        assert_eq!(
+            def.operands.len(),
+            args.len(),
+            "wrong operands for `{}`",
+            def.name
+        );
+        Ops { def, args }
+    }
+
+    pub fn acc(x: i32) -> Ops {
+        Ops::new(ACC, vec![Operand::Imm(x)])
+    }
+
+    pub fn jmp(x: i32) -> Ops {
+        Ops::new(JMP, vec![Operand::Imm(x)])
+    }
+
+    pub fn nop(x: i32) -> Ops {
+        Ops::new(NOP, vec![Operand::Imm(x)])
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.def.name
+    }
+
+    /// The fixed offset operand `k` jumps by
+    pub fn offset(&self, k: usize) -> Option<i32> {
+        match self.args.get(k) {
+            Some(&Operand::Imm(x)) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// The `jmp` for a `nop` and the other way round
+    pub fn flipped(&self) -> Option<Ops> {
+        match self.name() {
+            "jmp" => Some(Ops::new(NOP, self.args.clone())),
+            "nop" => Some(Ops::new(JMP, self.args.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Ops {
+    fn eq(&self, other: &Ops) -> bool {
+        self.def.name == other.def.name && self.args == other.args
+    }
+}
+
+impl fmt::Display for Ops {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.def.name)?;
+        for (i, arg) in self.args.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { " " } else { ", " }, arg)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The opcodes a program may use, and how a machine running them decides it
+/// is stuck. The interpreter only ever calls their handlers, so new ones can
+/// be registered without touching it.
+#[derive(Debug, Clone)]
+pub struct InstructionSet {
+    defs: Vec<OpDef>,
+    loop_detector: Option<LoopDetector>,
+}
+
+impl InstructionSet {
+    /// No opcodes at all, and no loop detection
+    pub fn empty() -> InstructionSet {
+        InstructionSet {
+            defs: vec![],
+            loop_detector: None,
+        }
+    }
+
+    /// The handheld console's `acc`, `jmp` and `nop`. With nothing but those
+    /// a program that runs an instruction twice is stuck, so it stops there.
+    pub fn handheld() -> InstructionSet {
+        InstructionSet {
+            defs: vec![ACC, JMP, NOP],
+            loop_detector: Some(LoopDetector::Revisit),
+        }
+    }
+
+    /// What a `Machine` running these instructions should treat as a loop
+    pub fn loop_detector(&self) -> Option<LoopDetector> {
+        self.loop_detector
+    }
+
+    /// Change the loop detection, e.g. once a conditional jump makes
+    /// revisiting an instruction normal
+    pub fn set_loop_detector(&mut self, detector: Option<LoopDetector>) {
+        self.loop_detector = detector;
+    }
+
+    pub fn register(&mut self, def: OpDef) -> Result<()> {
+        if self.get(def.name).is_some() {
+            return Err(format!("`{}` is already an instruction", def.name).into());
+        }
+
+        self.defs.push(def);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&OpDef> {
+        self.defs.iter().find(|d| d.name == name)
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.defs.iter().map(|d| d.name).collect()
+    }
+
+    /// What an unknown mnemonic should have been, e.g. "`acc`, `jmp` or `nop`"
+    fn expected(&self) -> String {
+        let names: Vec<_> = self.defs.iter().map(|d| format!("`{}`", d.name)).collect();
+
+        match names.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+            None => "no instruction, as none are registered".to_string(),
+        }
+    }
+
+    /// Parse an instruction like `jmp -4`, `line` being a slice of `input`.
+    /// `label` resolves a `Kind::Target` operand that is not a number to an
+    /// offset, or `None` if there is no such label.
+    pub fn parse_with<F>(&self, input: &str, line: &str, mut label: F) -> Result<Ops>
+    where
+        F: FnMut(&str) -> Option<i32>,
+    {
+        let mut words = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|w| !w.is_empty());
+
+        let op = words
+            .next()
+            .ok_or_else(|| ParseError::at(input, line, "an instruction"))?;
+        let def = *self
+            .get(op)
+            .ok_or_else(|| ParseError::at(input, op, self.expected()))?;
+
+        let mut args = Vec::with_capacity(def.operands.len());
+        for kind in def.operands {
+            let word = words
+                .next()
+                .ok_or_else(|| ParseError::at(input, &line[line.len()..], kind.expected()))?;
+
+            let register = match word.as_bytes() {
+                [r @ b'a'..=b'z'] => Some(Operand::Reg(r - b'a')),
+                _ => None,
+            };
+            let number = || parse_at(input, word, kind.expected()).map(Operand::Imm);
+
+            let arg = match kind {
+                Kind::Imm => number()?,
+                Kind::Reg => {
+                    register.ok_or_else(|| ParseError::at(input, word, kind.expected()))?
+                }
+                Kind::Value => register.map_or_else(number, Ok)?,
+                Kind::Target => match label(word) {
+                    Some(offset) => Operand::Imm(offset),
+                    None => number()?,
+                },
+            };
+            args.push(arg);
+        }
+
+        if let Some(extra) = words.next() {
+            return Err(ParseError::at(input, extra, "the end of the instruction").into());
+        }
+
+        Ok(Ops::new(def, args))
+    }
+
+    /// Parse an instruction with no labels to resolve
+    pub fn parse(&self, input: &str, line: &str) -> Result<Ops> {
+        self.parse_with(input, line, |_| None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Analysis, Machine, Program, Status};
+
+    #[test]
+    fn register_test() {
+        let mut set = InstructionSet::handheld();
+        let defs = [
+            OpDef {
+                name: "inc",
+                operands: &[Kind::Reg],
+                flow: Flow::Next,
+                exec: |cpu, args| {
+                    *cpu.register_mut(args[0])? += 1;
+                    Ok(1)
+                },
+            },
+            OpDef {
+                name: "dec",
+                operands: &[Kind::Reg],
+                flow: Flow::Next,
+                exec: |cpu, args| {
+                    *cpu.register_mut(args[0])? -= 1;
+                    Ok(1)
+                },
+            },
+            OpDef {
+                name: "jnz",
+                operands: &[Kind::Value, Kind::Target],
+                flow: Flow::Branch(1),
+                exec: |cpu, args| match cpu.value(args[0])? {
+                    0 => Ok(1),
+                    _ => Ok(cpu.value(args[1])? as isize),
+                },
+            },
+            OpDef {
+                name: "out",
+                operands: &[Kind::Value],
+                flow: Flow::Next,
+                exec: |cpu, args| {
+                    let v = cpu.value(args[0])?;
+                    cpu.output.push(v);
+                    Ok(1)
+                },
+            },
+        ];
+        for def in defs.iter() {
+            set.register(*def).unwrap();
+        }
+        assert!(set.register(ACC).is_err());
+
+        let source = "inc a\ninc a\nloop: out a\ndec a\njnz a, loop\nacc +1\n";
+        let program = Program::parse(source, &set).unwrap();
+        assert_eq!(program.ops[4].to_string(), "jnz a, -2");
+
+        // The handheld console's loop detection stops a countdown the first
+        // time it goes round, so a set with `jnz` needs a step limit instead
+        let mut machine = Machine::new(program.ops.clone(), &set);
+        assert_eq!(machine.run(), Status::Looped);
+        assert_eq!(machine.cpu().output, [2]);
+        assert!(Analysis::new(&program.ops).is_err());
+
+        set.set_loop_detector(Some(LoopDetector::StepLimit(100)));
+        let mut machine = Machine::new(program.ops.clone(), &set);
+        assert_eq!(machine.run(), Status::Halted);
+        assert_eq!(machine.cpu().output, [2, 1]);
+        assert_eq!((machine.acc(), machine.steps()), (1, 9));
+
+        let spin = Program::parse("jnz 1, +0\n", &set).unwrap();
+        let mut machine = Machine::new(spin.ops, &set);
+        assert_eq!(machine.run(), Status::OutOfSteps);
+        assert_eq!(machine.steps(), 100);
+
+        // Instructions built by hand can name registers that do not exist
+        set.set_loop_detector(None);
+        let mut machine = Machine::new(
+            vec![Ops::new(*set.get("inc").unwrap(), vec![Operand::Imm(3)])],
+            &set,
+        );
+        assert_eq!(machine.run(), Status::Faulted);
+        assert_eq!(machine.fault(), Some("+3 is not a register"));
+
+        let overflow = Program::parse("acc +2147483647\nacc +1\n", &set).unwrap();
+        let mut machine = Machine::new(overflow.ops, &set);
+        assert_eq!(machine.run(), Status::Faulted);
+        assert_eq!(machine.fault(), Some("the accumulator overflows"));
+
+        let e = Program::parse("inc 3\n", &set).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 5: expected a register `a` to `z`, found `3`"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use aoc_core::{Result, Solution};
+
+pub use crate::analysis::{Analysis, Repair};
+pub use crate::asm::Program;
+pub use crate::isa::{InstructionSet, Ops};
+pub use crate::machine::{Breakpoint, LoopDetector, Machine, Status};
+
+pub mod analysis;
+pub mod asm;
+pub mod debugger;
+pub mod isa;
+pub mod machine;
+
+fn parse_input(input: &str) -> Result<Vec<Ops>> {
+    Ok(Program::parse(input, &InstructionSet::handheld())?.ops)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input<'a> = Vec<Ops>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Ops>> {
+        parse_input(input)
+    }
+
+    fn part1(operations: &Vec<Ops>) -> Result<i32> {
+        let mut machine = Machine::new(operations.clone(), &InstructionSet::handheld());
+
+        match machine.run() {
+            Status::Looped => Ok(machine.acc()),
+            status => Err(format!("the program never loops: {}", status).into()),
+        }
+    }
+
+    fn part2(operations: &Vec<Ops>) -> Result<i32> {
+        let repair = Analysis::new(operations)?
+            .repairs()
+            .into_iter()
+            .next()
+            .ok_or("no single swap makes the program terminate")?;
+
+        let mut machine = Machine::new(operations.clone(), &InstructionSet::handheld());
+        machine.patch(repair.index, repair.to)?;
+
+        match machine.run() {
+            Status::Halted => Ok(machine.acc()),
+            status => Err(format!("the repaired program {}", status).into()),
+        }
+    }
+}
@@ -0,0 +1,311 @@
+use std::fmt;
+
+use aoc_core::Result;
+
+use crate::isa::{Cpu, InstructionSet, Ops};
+
+/// Where a `Machine` stands before its next instruction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    /// It can carry on
+    Running,
+    /// It reached the instruction just past the end, so the program finished
+    Halted,
+    /// Its next instruction has run before, so it would go round forever
+    Looped,
+    /// It has run as many steps as its `LoopDetector::StepLimit` allows
+    OutOfSteps,
+    /// An instruction failed, e.g. by naming a register that does not exist
+    Faulted,
+    /// It jumped somewhere other than the program or the instruction just past it
+    OutOfBounds,
+    /// It stopped at a breakpoint
+    Break(Breakpoint),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Running => write!(f, "running"),
+            Status::Halted => write!(f, "halted"),
+            Status::Looped => write!(f, "about to repeat an instruction"),
+            Status::OutOfSteps => write!(f, "out of steps"),
+            Status::Faulted => write!(f, "stopped by an error"),
+            Status::OutOfBounds => write!(f, "jumped out of the program"),
+            Status::Break(b) => write!(f, "stopped at breakpoint {}", b),
+        }
+    }
+}
+
+/// How a `Machine` decides a program is stuck
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoopDetector {
+    /// Stop before running any instruction a second time, which is right for
+    /// programs without conditional jumps
+    Revisit,
+    /// Stop after this many steps
+    StepLimit(usize),
+}
+
+/// A condition that stops `Machine::run`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Breakpoint {
+    /// The next instruction is this one
+    At(usize),
+    /// An instruction has just set the accumulator to this value
+    Acc(i32),
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Breakpoint::At(i) => write!(f, "at {}", i),
+            Breakpoint::Acc(v) => write!(f, "acc = {}", v),
+        }
+    }
+}
+
+/// One executed instruction
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+    pub ip: usize,
+    pub op: Ops,
+    /// The accumulator after the instruction ran
+    pub acc: i32,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>5}: {:<10} acc {}",
+            self.ip,
+            self.op.to_string(),
+            self.acc
+        )
+    }
+}
+
+/// The handheld console, running a boot program one instruction at a time
+#[derive(Debug, Clone)]
+pub struct Machine {
+    program: Vec<Ops>,
+    ip: isize,
+    cpu: Cpu,
+    /// Whether each instruction has run since the last reset
+    seen: Vec<bool>,
+    steps: usize,
+    breakpoints: Vec<Breakpoint>,
+    trace: Option<Vec<TraceEntry>>,
+    loop_detector: Option<LoopDetector>,
+    /// Why the last instruction failed, if it did
+    fault: Option<String>,
+}
+
+impl Machine {
+    /// A machine for `program`, written with the instructions of `set`, that
+    /// stops a running program when `set.loop_detector()` says it is stuck
+    pub fn new(program: Vec<Ops>, set: &InstructionSet) -> Machine {
+        Machine {
+            seen: vec![false; program.len()],
+            program,
+            ip: 0,
+            cpu: Cpu::default(),
+            steps: 0,
+            breakpoints: vec![],
+            trace: None,
+            loop_detector: set.loop_detector(),
+            fault: None,
+        }
+    }
+
+    /// Start again from the first instruction with every register zeroed,
+    /// keeping the program, breakpoints and whether tracing is on
+    pub fn reset(&mut self) {
+        self.ip = 0;
+        self.cpu = Cpu::default();
+        self.steps = 0;
+        self.fault = None;
+        self.seen.iter_mut().for_each(|s| *s = false);
+        if let Some(trace) = &mut self.trace {
+            trace.clear();
+        }
+    }
+
+    pub fn program(&self) -> &[Ops] {
+        &self.program
+    }
+
+    /// The index of the next instruction; negative once it has jumped off the front
+    pub fn ip(&self) -> isize {
+        self.ip
+    }
+
+    pub fn acc(&self) -> i32 {
+        self.cpu.acc
+    }
+
+    /// The accumulator, registers and output
+    pub fn cpu(&self) -> &Cpu {
+        &self.cpu
+    }
+
+    /// Instructions run since the last reset
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Replace instruction `i`
+    pub fn patch(&mut self, i: usize, op: Ops) -> Result<()> {
+        let len = self.program.len();
+        let slot = self
+            .program
+            .get_mut(i)
+            .ok_or_else(|| format!("there is no instruction {} in a program of {}", i, len))?;
+        *slot = op;
+
+        Ok(())
+    }
+
+    pub fn add_breakpoint(&mut self, b: Breakpoint) {
+        if !self.breakpoints.contains(&b) {
+            self.breakpoints.push(b);
+        }
+    }
+
+    /// Whether there was such a breakpoint to remove
+    pub fn remove_breakpoint(&mut self, b: Breakpoint) -> bool {
+        let before = self.breakpoints.len();
+        self.breakpoints.retain(|&x| x != b);
+        self.breakpoints.len() != before
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    /// Start or stop recording every instruction run; stopping drops the trace
+    pub fn set_tracing(&mut self, on: bool) {
+        self.trace = if on {
+            Some(self.trace.take().unwrap_or_default())
+        } else {
+            None
+        };
+    }
+
+    /// The instructions run since tracing started or the last reset
+    pub fn trace(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or(&[])
+    }
+
+    /// The error that stopped the machine, while `Status::Faulted`
+    pub fn fault(&self) -> Option<&str> {
+        self.fault.as_deref()
+    }
+
+    /// Whether the machine can run its next instruction, ignoring breakpoints
+    pub fn status(&self) -> Status {
+        let len = self.program.len() as isize;
+
+        if self.fault.is_some() {
+            return Status::Faulted;
+        } else if self.ip == len {
+            return Status::Halted;
+        } else if !(0..len).contains(&self.ip) {
+            return Status::OutOfBounds;
+        }
+
+        match self.loop_detector {
+            Some(LoopDetector::Revisit) if self.seen[self.ip as usize] => Status::Looped,
+            Some(LoopDetector::StepLimit(n)) if self.steps >= n => Status::OutOfSteps,
+            _ => Status::Running,
+        }
+    }
+
+    fn breakpoint(&self, old_acc: i32) -> Option<Breakpoint> {
+        self.breakpoints.iter().copied().find(|&b| match b {
+            Breakpoint::At(i) => self.ip == i as isize,
+            Breakpoint::Acc(v) => self.cpu.acc == v && old_acc != v,
+        })
+    }
+
+    /// Run the next instruction, if the machine is still running, and say
+    /// where that leaves it; breakpoints are ignored
+    pub fn step(&mut self) -> Status {
+        if self.status() != Status::Running {
+            return self.status();
+        }
+
+        let ip = self.ip as usize;
+        let op = &self.program[ip];
+        self.seen[ip] = true;
+        self.steps += 1;
+
+        match (op.def.exec)(&mut self.cpu, &op.args) {
+            Ok(offset) => self.ip += offset,
+            Err(e) => self.fault = Some(e.to_string()),
+        }
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                ip,
+                op: op.clone(),
+                acc: self.cpu.acc,
+            });
+        }
+
+        self.status()
+    }
+
+    /// Step until the program halts, loops, leaves its bounds or fails, or a
+    /// breakpoint is hit after at least one step
+    pub fn run(&mut self) -> Status {
+        loop {
+            let old_acc = self.cpu.acc;
+            let status = self.step();
+            if status != Status::Running {
+                return status;
+            }
+
+            if let Some(b) = self.breakpoint(old_acc) {
+                return Status::Break(b);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn machine_test() {
+        let input = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+        let mut m = Machine::new(
+            crate::parse_input(input).unwrap(),
+            &InstructionSet::handheld(),
+        );
+
+        m.set_tracing(true);
+        m.add_breakpoint(Breakpoint::At(3));
+        m.add_breakpoint(Breakpoint::Acc(2));
+
+        assert_eq!(m.run(), Status::Break(Breakpoint::Acc(2)));
+        assert_eq!((m.ip(), m.steps()), (7, 4));
+        assert_eq!(m.run(), Status::Break(Breakpoint::At(3)));
+        assert_eq!(m.step(), Status::Running);
+        assert_eq!(m.acc(), 5);
+
+        assert!(m.remove_breakpoint(Breakpoint::At(3)));
+        assert_eq!(m.run(), Status::Looped);
+        assert_eq!(m.trace().len(), 7);
+        assert_eq!(m.trace()[1].to_string(), "    1: acc +1     acc 1");
+
+        m.patch(7, Ops::nop(-4)).unwrap();
+        m.reset();
+        assert_eq!(m.run(), Status::Break(Breakpoint::Acc(2)));
+        assert_eq!(m.run(), Status::Halted);
+        assert_eq!(m.acc(), 8);
+        assert!(m.patch(9, Ops::nop(0)).is_err());
+    }
+}
@@ -1,123 +1,113 @@
-use std::collections::HashSet;
-use std::io::{self, Read};
+use std::io::{self, BufRead, Write};
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+use aoc08::debugger::Debugger;
+use aoc08::{Analysis, Day08, InstructionSet, Machine, Program};
+use aoc_core::Solution;
 
-#[derive(Clone, PartialEq)]
-enum Ops {
-    ACC(i32),
-    JMP(i32),
-    NOP(i32),
-}
-
-enum LoadStatus {
-    COMPLETE,
-    BUG,
-}
+const USAGE: &str =
+    "Usage: aoc08 [--debug <program> | --analyse | --assemble | --disassemble] < program";
 
-#[derive(Debug, Default)]
-struct BootProc {
-    idx: i32,
-    seen: HashSet<i32>,
-    acc: i32,
-}
-
-impl BootProc {
-    fn reset(&mut self) {
-        self.idx = 0;
-        self.seen.clear();
-        self.acc = 0;
-    }
+/// Runs of consecutive indices, e.g. `3, 5-8`
+fn ranges(indices: &[usize]) -> String {
+    let mut runs: Vec<(usize, usize)> = vec![];
 
-    fn process_operations(&mut self, ops: &[Ops]) -> LoadStatus {
-        loop {
-            if !self.seen.insert(self.idx) {
-                return LoadStatus::BUG;
-            }
-
-            if self.idx >= ops.len() as i32 {
-                return LoadStatus::COMPLETE;
-            }
-
-            let o = ops.get(self.idx as usize).expect("operation out of bounds");
-            match o {
-                Ops::NOP(_) => self.idx += 1,
-                Ops::JMP(x) => self.idx += x,
-                Ops::ACC(x) => {
-                    self.idx += 1;
-                    self.acc += x;
-                }
-            }
+    for &i in indices {
+        match runs.last_mut() {
+            Some((_, end)) if *end + 1 == i => *end = i,
+            _ => runs.push((i, i)),
         }
     }
-}
 
-fn parse_input(input: &str) -> Result<Vec<Ops>> {
-    let o = input
-        .lines()
-        .map(|l| {
-            let mut iter = l.split_whitespace();
-            let op = iter.next().ok_or("")?;
-            let num = iter.next().ok_or("")?.parse::<i32>()?;
-
-            match op {
-                "acc" => Ok(Ops::ACC(num)),
-                "jmp" => Ok(Ops::JMP(num)),
-                "nop" => Ok(Ops::NOP(num)),
-                _ => unreachable!(),
-            }
+    runs.iter()
+        .map(|&(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
         })
-        .collect::<Result<Vec<_>>>()?;
-    Ok(o)
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
-
-    let start = std::time::Instant::now();
-    part1(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
-
-    let start = std::time::Instant::now();
-    part2(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
+fn analyse() -> aoc_core::Result<()> {
+    let input = aoc_core::input::read_stdin()?;
+    let analysis = Analysis::new(&Day08::parse(&input)?)?;
+
+    println!(
+        "terminates: {}, {} instructions run",
+        analysis.terminates(),
+        analysis.path().len()
+    );
+    for repair in analysis.repairs() {
+        println!("repair {}", repair);
+    }
+    println!("dead code: {}", ranges(&analysis.dead_code()));
+    for cycle in analysis.cycles() {
+        println!("loop of {}: {}", cycle.len(), ranges(&cycle));
+    }
 
     Ok(())
 }
 
-fn part1(input: &str) -> Result<()> {
-    let operations = parse_input(input)?;
-    let mut boot_proc: BootProc = Default::default();
-    boot_proc.process_operations(&operations);
-
-    println!("part 1 solution: {}", boot_proc.acc);
+/// Print the program on stdin with every jump to a label, or with labels
+/// and comments stripped back to the puzzle's plain format
+fn assemble(labels: bool) -> aoc_core::Result<()> {
+    let input = aoc_core::input::read_stdin()?;
+    let mut program = Program::parse(&input, &InstructionSet::handheld())?;
+
+    if labels {
+        program.label_jumps();
+        print!("{}", program);
+    } else {
+        print!("{}", Program::from_ops(program.ops));
+    }
 
     Ok(())
 }
 
-fn part2(input: &str) -> Result<()> {
-    let mut operations = parse_input(input)?;
-    let mut boot_proc: BootProc = Default::default();
+fn debug(path: &str) -> aoc_core::Result<()> {
+    let input = aoc_core::input::read_file(path)?;
+    let mut debugger = Debugger::new(Machine::new(
+        Day08::parse(&input)?,
+        &InstructionSet::handheld(),
+    ));
+    println!("{}", debugger.position());
 
-    for i in 0..operations.len() {
-        let o = &mut operations[i];
-        let op_rep = match o {
-            Ops::NOP(x) => Ops::JMP(*x),
-            Ops::JMP(x) => Ops::NOP(*x),
-            Ops::ACC(_) => continue,
-        };
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
 
-        boot_proc.reset();
-        let opx = std::mem::replace(o, op_rep);
+    loop {
+        print!("(aoc08) ");
+        io::stdout().flush()?;
 
-        if let LoadStatus::COMPLETE = boot_proc.process_operations(&operations) {
-            println!("part 2 solution: {}", boot_proc.acc);
-            return Ok(());
-        }
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
 
-        operations[i] = opx;
+        match debugger.execute(&line) {
+            Ok(Some(out)) if out.is_empty() => (),
+            Ok(Some(out)) => println!("{}", out),
+            Ok(None) => break,
+            Err(e) => println!("error: {}", e),
+        }
     }
 
-    Err("part 2 NO SOLUTION FOUND".to_string().into())
+    Ok(())
+}
+
+/// With no arguments, solve both parts. `--analyse` reports how the program
+/// on stdin can be repaired, what never runs and every loop in it;
+/// `--disassemble` prints it with labels and `--assemble` without. `--debug`
+/// loads a boot program and reads debugger commands from stdin; `help` lists
+/// them
+fn main() -> aoc_core::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match &args[..] {
+        [] => aoc_core::main::<Day08>(),
+        [flag] if flag == "--analyse" => analyse(),
+        [flag] if flag == "--assemble" => assemble(false),
+        [flag] if flag == "--disassemble" => assemble(true),
+        [flag, path] if flag == "--debug" => debug(path),
+        _ => Err(USAGE.into()),
+    }
 }
@@ -0,0 +1,228 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
+use std::ops::Range;
+
+use aoc_core::{input, ParseError, Result, Solution};
+
+/// The puzzle's preamble length
+pub const PREAMBLE: usize = 25;
+
+/// What `XmasValidator::push` made of a number
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    /// One of the first `preamble` numbers, which need not be a sum
+    Preamble,
+    Valid,
+    /// Not the sum of two different numbers among the `preamble` before it
+    Invalid,
+}
+
+/// Checks XMAS numbers as they arrive against the `preamble` numbers before
+/// each one. The window is a multiset, so a number that appears twice is
+/// still there after the older copy leaves.
+#[derive(Debug, Clone)]
+pub struct XmasValidator {
+    preamble: usize,
+    window: VecDeque<u64>,
+    counts: HashMap<u64, usize>,
+}
+
+impl XmasValidator {
+    pub fn new(preamble: usize) -> XmasValidator {
+        XmasValidator {
+            preamble,
+            window: VecDeque::with_capacity(preamble + 1),
+            counts: HashMap::with_capacity(preamble + 1),
+        }
+    }
+
+    /// Whether `n` is the sum of two numbers of different values in the window
+    pub fn is_sum(&self, n: u64) -> bool {
+        self.counts
+            .keys()
+            .any(|&x| matches!(n.checked_sub(x), Some(y) if y != x && self.counts.contains_key(&y)))
+    }
+
+    /// Check `n`, then slide it into the window
+    pub fn push(&mut self, n: u64) -> Verdict {
+        let verdict = if self.window.len() < self.preamble {
+            Verdict::Preamble
+        } else if self.is_sum(n) {
+            Verdict::Valid
+        } else {
+            Verdict::Invalid
+        };
+
+        self.window.push_back(n);
+        *self.counts.entry(n).or_insert(0) += 1;
+
+        if self.window.len() > self.preamble {
+            if let Some(oldest) = self.window.pop_front() {
+                if let Some(c) = self.counts.get_mut(&oldest) {
+                    *c -= 1;
+                    if *c == 0 {
+                        self.counts.remove(&oldest);
+                    }
+                }
+            }
+        }
+
+        verdict
+    }
+}
+
+/// A number that failed the check
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Invalid {
+    /// 1-based
+    pub line: usize,
+    pub value: u64,
+}
+
+/// Read one number per line from `reader`, yielding each as it is checked
+/// with the line it came from
+pub fn validate<R: Read>(
+    reader: R,
+    preamble: usize,
+) -> impl Iterator<Item = Result<(usize, u64, Verdict)>> {
+    let mut validator = XmasValidator::new(preamble);
+
+    BufReader::new(reader)
+        .lines()
+        .enumerate()
+        .map(move |(i, line)| {
+            let line = line?;
+            let n: u64 = line
+                .parse()
+                .map_err(|_| ParseError::new(i + 1, 1, line.as_str(), "a number"))?;
+
+            Ok((i + 1, n, validator.push(n)))
+        })
+}
+
+/// Every number in `data` that is not a sum of two before it
+pub fn invalid_numbers(data: &[u64], preamble: usize) -> Vec<Invalid> {
+    let mut validator = XmasValidator::new(preamble);
+
+    data.iter()
+        .enumerate()
+        .filter(|&(_, &n)| validator.push(n) == Verdict::Invalid)
+        .map(|(i, &value)| Invalid { line: i + 1, value })
+        .collect()
+}
+
+/// Every run of at least two consecutive numbers summing to `target`, by
+/// where it ends and then where it starts. Matching prefix sums through a
+/// map takes one pass, plus the time to list what it finds. Fails if the
+/// running total overflows.
+pub fn ranges_summing_to(data: &[u64], target: u64) -> Result<Vec<Range<usize>>> {
+    // Where each running total was reached, before the number at that index
+    let mut starts: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut ranges = vec![];
+    let mut sum: u64 = 0;
+
+    for (end, &n) in data.iter().enumerate() {
+        starts.entry(sum).or_default().push(end);
+        sum = sum
+            .checked_add(n)
+            .ok_or_else(|| format!("the numbers up to line {} add up to too much", end + 1))?;
+
+        if let Some(found) = sum.checked_sub(target).and_then(|s| starts.get(&s)) {
+            ranges.extend(
+                found
+                    .iter()
+                    .filter(|&&start| end > start)
+                    .map(|&start| start..end + 1),
+            );
+        }
+    }
+
+    Ok(ranges)
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input<'a> = Vec<u64>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<u64>> {
+        input::parse_lines(input)
+    }
+
+    fn part1(data: &Vec<u64>) -> Result<u64> {
+        let invalid = invalid_numbers(data, PREAMBLE);
+        let first = invalid.first().ok_or("no invalid number found")?;
+
+        Ok(first.value)
+    }
+
+    fn part2(data: &Vec<u64>) -> Result<u64> {
+        let target = Day09::part1(data)?;
+        let range = ranges_summing_to(data, target)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("no contiguous range sums to {}", target))?;
+
+        let run = &data[range];
+        let min = run.iter().min().ok_or("empty range")?;
+        let max = run.iter().max().ok_or("empty range")?;
+
+        Ok(min + max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n299\n277\n309\n576\n";
+
+    #[test]
+    fn validator_test() {
+        let data = Day09::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            invalid_numbers(&data, 5),
+            [Invalid {
+                line: 15,
+                value: 127
+            }]
+        );
+        assert_eq!(
+            ranges_summing_to(&data, 127).unwrap(),
+            vec![Range { start: 2, end: 6 }]
+        );
+
+        let streamed: Vec<_> = validate(EXAMPLE.as_bytes(), 5)
+            .filter_map(|r| r.ok())
+            .filter(|&(_, _, v)| v == Verdict::Invalid)
+            .collect();
+        assert_eq!(streamed, [(15, 127, Verdict::Invalid)]);
+
+        // The first 1 leaving the window must not take the second with it
+        let mut v = XmasValidator::new(3);
+        for &n in &[1, 1, 2] {
+            assert_eq!(v.push(n), Verdict::Preamble);
+        }
+        assert_eq!(v.push(3), Verdict::Valid);
+        assert_eq!(v.push(3), Verdict::Valid);
+        assert_eq!(v.push(2), Verdict::Invalid);
+
+        assert_eq!(
+            ranges_summing_to(&[1, 0, 2, 1], 3).unwrap(),
+            [0..3, 1..4, 2..4]
+        );
+        let e = ranges_summing_to(&[1, u64::MAX, 2], 3).unwrap_err();
+        assert_eq!(e.to_string(), "the numbers up to line 2 add up to too much");
+
+        let e = validate("1\nx\n".as_bytes(), 5)
+            .nth(1)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 1: expected a number, found `x`"
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use num_bigint::{BigUint, RandBigInt};
+use rand::Rng;
+
+use aoc_core::parse::parse_at;
+use aoc_core::{Result, Solution};
+
+/// The puzzle's adapters take an input 1, 2 or 3 jolts below their rating
+pub const DIFFS: [u64; 3] = [1, 2, 3];
+
+/// How many steps of each size a chain takes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Distribution {
+    counts: BTreeMap<u64, usize>,
+}
+
+impl Distribution {
+    pub fn count(&self, diff: u64) -> usize {
+        self.counts.get(&diff).copied().unwrap_or(0)
+    }
+
+    /// Each step size taken, smallest first, with how often
+    pub fn iter(&self) -> impl Iterator<Item = (u64, usize)> + '_ {
+        self.counts.iter().map(|(&d, &n)| (d, n))
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<_> = self.iter().map(|(d, n)| format!("{} x {}", n, d)).collect();
+
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// A bag of adapters between the wall outlet and the device. Each step up a
+/// chain must be one of the allowed differences.
+#[derive(Debug, Clone)]
+pub struct AdapterChain {
+    /// The outlet, every adapter in order, then the device
+    joltages: Vec<u64>,
+    diffs: Vec<u64>,
+    /// How many ways there are from each joltage on to the device
+    to_device: Vec<BigUint>,
+}
+
+impl AdapterChain {
+    /// An outlet of 0 jolts and a device rated the largest allowed difference
+    /// above the best adapter, as in the puzzle
+    pub fn new(adapters: &[u64], diffs: &[u64]) -> Result<AdapterChain> {
+        let top = adapters.iter().copied().max().unwrap_or(0);
+        let step = diffs
+            .iter()
+            .copied()
+            .max()
+            .ok_or("no differences are allowed")?;
+
+        AdapterChain::with_ends(adapters, diffs, 0, top + step)
+    }
+
+    pub fn with_ends(
+        adapters: &[u64],
+        diffs: &[u64],
+        outlet: u64,
+        device: u64,
+    ) -> Result<AdapterChain> {
+        let mut diffs = diffs.to_vec();
+        diffs.sort_unstable();
+        diffs.dedup();
+
+        if diffs.first() == Some(&0) {
+            return Err("a difference of 0 would let a chain go on forever".into());
+        }
+
+        let mut joltages = adapters.to_vec();
+        joltages.sort_unstable();
+
+        if joltages.first().is_some_and(|&j| j <= outlet)
+            || joltages.last().is_some_and(|&j| j >= device)
+            || outlet >= device
+        {
+            return Err(format!(
+                "every adapter must be between the outlet at {} and the device at {}",
+                outlet, device
+            )
+            .into());
+        }
+
+        joltages.insert(0, outlet);
+        joltages.push(device);
+
+        let mut chain = AdapterChain {
+            joltages,
+            diffs,
+            to_device: vec![],
+        };
+        chain.to_device = chain.count_to_device();
+
+        Ok(chain)
+    }
+
+    pub fn joltages(&self) -> &[u64] {
+        &self.joltages
+    }
+
+    /// The joltages after `i` that an adapter at `i` can plug into
+    fn next(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        let from = self.joltages[i];
+        let max = self.diffs.last().copied().unwrap_or(0);
+
+        (i + 1..self.joltages.len())
+            .take_while(move |&j| self.joltages[j] - from <= max)
+            .filter(move |&j| self.diffs.binary_search(&(self.joltages[j] - from)).is_ok())
+    }
+
+    fn count_to_device(&self) -> Vec<BigUint> {
+        let n = self.joltages.len();
+        let mut ways = vec![BigUint::default(); n];
+        ways[n - 1] = BigUint::from(1_u32);
+
+        for i in (0..n - 1).rev() {
+            let total = self.next(i).map(|j| &ways[j]).sum();
+            ways[i] = total;
+        }
+
+        ways
+    }
+
+    /// The steps of the one chain that uses every adapter, or an error naming
+    /// the first gap it cannot bridge
+    pub fn distribution(&self) -> Result<Distribution> {
+        let mut dist = Distribution::default();
+
+        for w in self.joltages.windows(2) {
+            let diff = w[1] - w[0];
+            if self.diffs.binary_search(&diff).is_err() {
+                return Err(format!(
+                    "no chain uses every adapter: {} jolts from {} to {}",
+                    diff, w[0], w[1]
+                )
+                .into());
+            }
+            *dist.counts.entry(diff).or_insert(0) += 1;
+        }
+
+        Ok(dist)
+    }
+
+    /// How many chains lead from the outlet to the device
+    pub fn arrangements(&self) -> &BigUint {
+        &self.to_device[0]
+    }
+
+    /// Every chain from the outlet to the device, as the joltages it steps
+    /// through, lowest first. There may be far too many to list them all.
+    pub fn chains(&self) -> Chains<'_> {
+        let zero = BigUint::default();
+        let stack = if self.to_device[0] != zero {
+            vec![vec![0]]
+        } else {
+            vec![]
+        };
+
+        Chains { chain: self, stack }
+    }
+
+    /// A chain picked uniformly at random from all of them, if there are any
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<Vec<u64>> {
+        let zero = BigUint::default();
+        if self.to_device[0] == zero {
+            return None;
+        }
+
+        let mut i = 0;
+        let mut path = vec![self.joltages[0]];
+
+        while i + 1 < self.joltages.len() {
+            // Each way on is as likely as the number of chains through it
+            let mut pick = rng.gen_biguint_below(&self.to_device[i]);
+
+            for j in self.next(i) {
+                if pick < self.to_device[j] {
+                    i = j;
+                    break;
+                }
+                pick -= &self.to_device[j];
+            }
+
+            path.push(self.joltages[i]);
+        }
+
+        Some(path)
+    }
+}
+
+/// Iterator returned by `AdapterChain::chains`
+pub struct Chains<'a> {
+    chain: &'a AdapterChain,
+    /// Partial chains, as indices, still to extend
+    stack: Vec<Vec<usize>>,
+}
+
+impl<'a> Iterator for Chains<'a> {
+    type Item = Vec<u64>;
+
+    fn next(&mut self) -> Option<Vec<u64>> {
+        let zero = BigUint::default();
+        let last = self.chain.joltages.len() - 1;
+
+        while let Some(path) = self.stack.pop() {
+            let i = *path.last()?;
+            if i == last {
+                return Some(path.iter().map(|&k| self.chain.joltages[k]).collect());
+            }
+
+            // Push in reverse so the lowest next step comes out first, and
+            // skip dead ends
+            let next: Vec<_> = self.chain.next(i).collect();
+            for &j in next.iter().rev() {
+                if self.chain.to_device[j] != zero {
+                    let mut longer = path.clone();
+                    longer.push(j);
+                    self.stack.push(longer);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+pub fn parse_adapters(input: &str) -> Result<Vec<u64>> {
+    input
+        .lines()
+        .map(|line| Ok(parse_at(input, line, "an adapter joltage")?))
+        .collect()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = AdapterChain;
+    type Part1 = usize;
+    type Part2 = BigUint;
+
+    fn parse(input: &str) -> Result<AdapterChain> {
+        AdapterChain::new(&parse_adapters(input)?, &DIFFS)
+    }
+
+    fn part1(chain: &AdapterChain) -> Result<usize> {
+        let dist = chain.distribution()?;
+
+        Ok(dist.count(1) * dist.count(3))
+    }
+
+    fn part2(chain: &AdapterChain) -> Result<BigUint> {
+        Ok(chain.arrangements().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn chain_test() {
+        let chain = AdapterChain::new(&[16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4], &DIFFS).unwrap();
+        let dist = chain.distribution().unwrap();
+        assert_eq!(dist.to_string(), "7 x 1, 5 x 3");
+
+        let chains: Vec<_> = chain.chains().collect();
+        assert_eq!(chains.len(), 8);
+        assert_eq!(chains[0], chain.joltages());
+        assert_eq!(*chain.arrangements(), BigUint::from(8_u32));
+
+        let mut rng = StdRng::seed_from_u64(10);
+        for _ in 0..20 {
+            assert!(chains.contains(&chain.sample(&mut rng).unwrap()));
+        }
+
+        // Steps of 4 can skip adapters, steps of 1 cannot
+        let chain = AdapterChain::new(&[1, 2, 3, 4, 5, 6, 7, 8, 9], &[1, 4]).unwrap();
+        assert_eq!(chain.joltages().last(), Some(&13));
+        assert!(chain.chains().any(|c| c == [0, 1, 5, 9, 13]));
+        assert_eq!(BigUint::from(chain.chains().count()), *chain.arrangements());
+
+        // Long enough to overflow a u64
+        let adapters: Vec<u64> = (1..=200).collect();
+        let chain = AdapterChain::new(&adapters, &DIFFS).unwrap();
+        assert!(chain.arrangements().bits() > 64);
+
+        assert!(AdapterChain::new(&[1, 6], &DIFFS)
+            .unwrap()
+            .distribution()
+            .is_err());
+    }
+}
@@ -1,80 +1,85 @@
-use std::collections::HashMap;
-use std::io::{self, Read};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+use aoc10::AdapterChain;
 
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
+const USAGE: &str = "Usage: aoc10 [--diffs <d>,...] [--device <jolts>] [--list <n>] \
+    [--sample <n> [--seed <s>]] < input";
 
-    let start = std::time::Instant::now();
-    part1(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
-
-    let start = std::time::Instant::now();
-    part2(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
-
-    Ok(())
+fn parse_diffs(arg: Option<&String>) -> aoc_core::Result<Vec<u64>> {
+    arg.ok_or(USAGE)?
+        .split(',')
+        .map(|d| Ok(d.trim().parse()?))
+        .collect()
 }
 
-fn parse_input(input: &str) -> Result<Vec<usize>> {
-    let mut adapters = Vec::new();
-    adapters.push(0 as usize);
-
-    for line in input.lines() {
-        adapters.push(line.parse()?);
-    }
-
-    adapters.sort_unstable();
-    adapters.push(adapters[adapters.len() - 1] + 3);
-
-    Ok(adapters)
+fn parse_count(arg: Option<&String>) -> aoc_core::Result<u64> {
+    Ok(arg.ok_or(USAGE)?.parse()?)
 }
 
-fn part1(input: &str) -> Result<()> {
-    let adapters = parse_input(input)?;
-
-    let dist = adapters.windows(2).fold(HashMap::new(), |mut acc, v| {
-        let diff = v[1] - v[0];
-        let e = acc.entry(diff).or_insert(0);
-        *e += 1;
-
-        acc
-    });
-
-    println!("{:?}", dist);
-    let soln = dist.get(&1).unwrap_or(&0) * dist.get(&3).unwrap_or(&0);
-
-    println!("part 1 solution: {:?}", soln);
-
-    Ok(())
+fn show(chain: &[u64]) -> String {
+    chain
+        .iter()
+        .map(|j| j.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
-fn part2(input: &str) -> Result<()> {
-    let adapters = parse_input(input)?;
-    let num_adapters = adapters.len();
+/// With no arguments, solve both parts. Otherwise build a chain allowing the
+/// `--diffs` steps (1, 2 and 3 by default) up to the `--device`, and report
+/// how the full chain steps and how many chains there are; `--list` prints
+/// the first chains in order and `--sample` some picked at random
+fn main() -> aoc_core::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-    let mut path_acc: Vec<usize> = Vec::with_capacity(num_adapters);
-    path_acc.resize(num_adapters, 0);
+    if args.is_empty() {
+        return aoc_core::main::<aoc10::Day10>();
+    }
 
-    for (i, v) in adapters.iter().enumerate() {
-        match i {
-            0 => continue,
-            1..=3 => path_acc[i] += 1,
-            _ => (),
+    let mut diffs = aoc10::DIFFS.to_vec();
+    let mut device = None;
+    let mut list = 0;
+    let mut sample = 0;
+    let mut seed = None;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--diffs" => diffs = parse_diffs(it.next())?,
+            "--device" => device = Some(parse_count(it.next())?),
+            "--list" => list = parse_count(it.next())?,
+            "--sample" => sample = parse_count(it.next())?,
+            "--seed" => seed = Some(parse_count(it.next())?),
+            _ => return Err(USAGE.into()),
         }
+    }
 
-        let mut ci = i as isize - 1;
+    let input = aoc_core::input::read_stdin()?;
+    let adapters = aoc10::parse_adapters(&input)?;
+    let chain = match device {
+        Some(device) => AdapterChain::with_ends(&adapters, &diffs, 0, device)?,
+        None => AdapterChain::new(&adapters, &diffs)?,
+    };
 
-        while (ci > 0) && (v - adapters[ci as usize] <= 3) {
-            path_acc[i] += path_acc[ci as usize];
-            ci -= 1;
-        }
+    match chain.distribution() {
+        Ok(dist) => println!("every adapter: {}", dist),
+        Err(e) => println!("{}", e),
+    }
+    println!("{} chains", chain.arrangements());
+
+    for c in chain.chains().take(list as usize) {
+        println!("{}", show(&c));
     }
 
-    let num_arangements = path_acc[num_adapters - 1];
+    let mut rng = match seed {
+        Some(s) => StdRng::seed_from_u64(s),
+        None => StdRng::from_entropy(),
+    };
+    for _ in 0..sample {
+        if let Some(c) = chain.sample(&mut rng) {
+            println!("{}", show(&c));
+        }
+    }
 
-    println!("part 2 solution: {:?}", num_arangements);
     Ok(())
 }
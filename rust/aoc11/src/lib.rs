@@ -0,0 +1,434 @@
+use std::convert::Infallible;
+use std::fmt;
+
+use rayon::prelude::*;
+
+use aoc_core::grid::{Bounds, Dir, Grid, Pos, DIRS4, DIRS8};
+use aoc_core::{Result, Solution};
+
+pub mod render;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeatStatus {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl fmt::Display for SeatStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            SeatStatus::Occupied => '#',
+            SeatStatus::Empty => 'L',
+            SeatStatus::Floor => '.',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+/// How people choose seats: an empty seat fills when none of its neighbours
+/// are taken, and someone leaves when at least `tolerance` of them are
+pub trait SeatingRule: Sync {
+    /// The seats that count as neighbours of the seat at `pos`. Only called
+    /// once per seat, before the simulation starts.
+    fn neighbours(&self, room: &Grid<SeatStatus>, pos: Pos) -> Vec<Pos>;
+
+    fn tolerance(&self) -> usize;
+}
+
+/// The first seat in each of a set of directions, looking at most `reach`
+/// steps (or to the wall, for `None`) and seeing over the floor
+#[derive(Debug, Clone, PartialEq)]
+pub struct Neighbourhood {
+    pub dirs: Vec<Dir>,
+    pub reach: Option<usize>,
+    pub tolerance: usize,
+}
+
+impl Neighbourhood {
+    /// The 8 seats around, for part 1
+    pub fn adjacent() -> Neighbourhood {
+        Neighbourhood {
+            dirs: DIRS8.to_vec(),
+            reach: Some(1),
+            tolerance: 4,
+        }
+    }
+
+    /// The first seat visible in each of the 8 directions, for part 2
+    pub fn line_of_sight() -> Neighbourhood {
+        Neighbourhood {
+            dirs: DIRS8.to_vec(),
+            reach: None,
+            tolerance: 5,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: usize) -> Neighbourhood {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Only look up, down, left and right
+    pub fn orthogonal(mut self) -> Neighbourhood {
+        self.dirs = DIRS4.to_vec();
+        self
+    }
+}
+
+impl SeatingRule for Neighbourhood {
+    fn neighbours(&self, room: &Grid<SeatStatus>, pos: Pos) -> Vec<Pos> {
+        let reach = self.reach.unwrap_or(usize::MAX);
+
+        self.dirs
+            .iter()
+            .filter_map(|&dir| {
+                room.ray(pos, dir, Bounds::Clamp)
+                    .take(reach)
+                    .find(|&p| room[p] != SeatStatus::Floor)
+            })
+            .collect()
+    }
+
+    fn tolerance(&self) -> usize {
+        self.tolerance
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WaitingRoom {
+    seats: Grid<SeatStatus>,
+}
+
+impl WaitingRoom {
+    pub fn parse(input: &str) -> Result<WaitingRoom> {
+        let seats = Grid::parse(input, "`.`, `#` or `L`", |c| match c {
+            '.' => Some(SeatStatus::Floor),
+            '#' => Some(SeatStatus::Occupied),
+            'L' => Some(SeatStatus::Empty),
+            _ => None,
+        })?;
+
+        Ok(WaitingRoom { seats })
+    }
+
+    pub fn seats(&self) -> &Grid<SeatStatus> {
+        &self.seats
+    }
+}
+
+/// How a simulation ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    /// Nothing changes after `ticks` rounds, with `occupied` seats taken
+    Stable { ticks: usize, occupied: usize },
+    /// From round `start` on the room repeats itself every `period` rounds
+    Oscillating { start: usize, period: usize },
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Stable { ticks, occupied } => write!(
+                f,
+                "stable after {} rounds with {} seats occupied",
+                ticks, occupied
+            ),
+            Outcome::Oscillating { start, period } => {
+                write!(f, "repeats every {} rounds from round {}", period, start)
+            }
+        }
+    }
+}
+
+/// A waiting room filling up under a `SeatingRule`. Each seat's neighbours
+/// are worked out once; every round then reads one buffer and writes the
+/// other, a seat at a time in parallel.
+pub struct Simulation {
+    width: usize,
+    height: usize,
+    /// Every seat's position, skipping the floor
+    positions: Vec<Pos>,
+    /// Indices into `positions` of each seat's neighbours
+    neighbours: Vec<Vec<usize>>,
+    tolerance: usize,
+    occupied: Vec<bool>,
+    next: Vec<bool>,
+    ticks: usize,
+}
+
+impl Simulation {
+    pub fn new<R: SeatingRule + ?Sized>(room: &WaitingRoom, rule: &R) -> Simulation {
+        let seats = &room.seats;
+        let positions: Vec<Pos> = seats
+            .positions()
+            .filter(|&p| seats[p] != SeatStatus::Floor)
+            .collect();
+
+        let mut index = Grid::filled(seats.width(), seats.height(), usize::MAX);
+        for (i, &p) in positions.iter().enumerate() {
+            index[p] = i;
+        }
+
+        let neighbours = positions
+            .par_iter()
+            .map(|&p| {
+                rule.neighbours(seats, p)
+                    .into_iter()
+                    .map(|n| index[n])
+                    .filter(|&i| i != usize::MAX)
+                    .collect()
+            })
+            .collect();
+
+        let occupied: Vec<bool> = positions
+            .iter()
+            .map(|&p| seats[p] == SeatStatus::Occupied)
+            .collect();
+
+        Simulation {
+            width: seats.width(),
+            height: seats.height(),
+            next: occupied.clone(),
+            occupied,
+            positions,
+            neighbours,
+            tolerance: rule.tolerance(),
+            ticks: 0,
+        }
+    }
+
+    /// Rounds run so far
+    pub fn ticks(&self) -> usize {
+        self.ticks
+    }
+
+    pub fn num_occupied(&self) -> usize {
+        self.occupied.iter().filter(|&&o| o).count()
+    }
+
+    /// The room as it stands
+    pub fn room(&self) -> Grid<SeatStatus> {
+        let mut seats = Grid::filled(self.width, self.height, SeatStatus::Floor);
+
+        for (&p, &o) in self.positions.iter().zip(&self.occupied) {
+            seats[p] = if o {
+                SeatStatus::Occupied
+            } else {
+                SeatStatus::Empty
+            };
+        }
+
+        seats
+    }
+
+    /// Run one round, returning whether any seat changed
+    pub fn tick(&mut self) -> bool {
+        step(
+            &self.neighbours,
+            self.tolerance,
+            &self.occupied,
+            &mut self.next,
+        );
+
+        std::mem::swap(&mut self.occupied, &mut self.next);
+        self.ticks += 1;
+
+        self.occupied != self.next
+    }
+
+    /// Run rounds until the room stops changing or starts repeating itself.
+    ///
+    /// Repeats are found with Brent's algorithm, which only ever keeps a few rooms
+    /// however long the run, rather than one per round.
+    pub fn run(&mut self) -> Outcome {
+        match self.run_watching(|_| Ok::<(), Infallible>(())) {
+            Ok(outcome) => outcome,
+            Err(never) => match never {},
+        }
+    }
+
+    /// `run`, showing `watch` the simulation before every round it runs: each
+    /// one up to the room settling, or at least one full cycle if it repeats.
+    /// The first error `watch` returns stops the run.
+    pub fn run_watching<E, F>(&mut self, mut watch: F) -> std::result::Result<Outcome, E>
+    where
+        F: FnMut(&Simulation) -> std::result::Result<(), E>,
+    {
+        let first = self.ticks;
+        let initial = self.occupied.clone();
+        let mut tortoise = initial.clone();
+        let mut power = 1;
+        let mut period = 0;
+
+        // Find the period: the room eventually matches one saved at a power-of-two round
+        loop {
+            watch(self)?;
+
+            if !self.tick() {
+                return Ok(Outcome::Stable {
+                    ticks: self.ticks - 1,
+                    occupied: self.num_occupied(),
+                });
+            }
+            period += 1;
+
+            if self.occupied == tortoise {
+                break;
+            }
+
+            if period == power {
+                tortoise.copy_from_slice(&self.occupied);
+                power *= 2;
+                period = 0;
+            }
+        }
+
+        // Then the start: the first round that matches the one `period` rounds later
+        let mut scratch = initial.clone();
+        let mut advance = |room: &mut Vec<bool>| {
+            step(&self.neighbours, self.tolerance, room, &mut scratch);
+            std::mem::swap(room, &mut scratch);
+        };
+
+        let mut behind = initial;
+        let mut ahead = behind.clone();
+        for _ in 0..period {
+            advance(&mut ahead);
+        }
+
+        let mut start = first;
+        while behind != ahead {
+            advance(&mut behind);
+            advance(&mut ahead);
+            start += 1;
+        }
+
+        Ok(Outcome::Oscillating { start, period })
+    }
+}
+
+/// Work out the round after `occupied` into `next`
+fn step(neighbours: &[Vec<usize>], tolerance: usize, occupied: &[bool], next: &mut [bool]) {
+    next.par_iter_mut().enumerate().for_each(|(i, next)| {
+        let taken = neighbours[i].iter().filter(|&&n| occupied[n]).count();
+
+        *next = match occupied[i] {
+            false => taken == 0,
+            true => taken < tolerance,
+        };
+    });
+}
+
+fn settle<R: SeatingRule>(room: &WaitingRoom, rule: &R) -> Result<usize> {
+    match Simulation::new(room, rule).run() {
+        Outcome::Stable { occupied, .. } => Ok(occupied),
+        outcome => Err(format!("the room never settles: it {}", outcome).into()),
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = WaitingRoom;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<WaitingRoom> {
+        WaitingRoom::parse(input)
+    }
+
+    fn part1(room: &WaitingRoom) -> Result<usize> {
+        settle(room, &Neighbourhood::adjacent())
+    }
+
+    fn part2(room: &WaitingRoom) -> Result<usize> {
+        settle(room, &Neighbourhood::line_of_sight())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL
+";
+
+    #[test]
+    fn outcome_test() {
+        let room = WaitingRoom::parse(EXAMPLE).unwrap();
+
+        let mut sim = Simulation::new(&room, &Neighbourhood::adjacent());
+        assert_eq!(
+            sim.run(),
+            Outcome::Stable {
+                ticks: 5,
+                occupied: 37
+            }
+        );
+        assert_eq!(sim.room().to_string().lines().next(), Some("#.#L.L#.##"));
+
+        // Rows of nothing but floor are still part of the room
+        let sim = Simulation::new(
+            &WaitingRoom::parse("L.L\n...\n").unwrap(),
+            &Neighbourhood::adjacent(),
+        );
+        assert_eq!(sim.room().to_string(), "L.L\n...");
+
+        let mut sim = Simulation::new(&room, &Neighbourhood::line_of_sight());
+        assert_eq!(
+            sim.run(),
+            Outcome::Stable {
+                ticks: 6,
+                occupied: 26
+            }
+        );
+
+        // Two seats side by side that cannot bear any company fill and empty together
+        let pair = WaitingRoom::parse("LL\n").unwrap();
+        let mut sim = Simulation::new(&pair, &Neighbourhood::adjacent().with_tolerance(1));
+        assert_eq!(
+            sim.run(),
+            Outcome::Oscillating {
+                start: 0,
+                period: 2
+            }
+        );
+
+        // Watching sees every round up to the repeat, so at least one full cycle
+        let mut sim = Simulation::new(&pair, &Neighbourhood::adjacent().with_tolerance(1));
+        let mut watched = vec![];
+        let outcome = sim.run_watching(|sim| {
+            watched.push((sim.ticks(), sim.num_occupied()));
+            Ok::<(), ()>(())
+        });
+        assert!(matches!(outcome, Ok(Outcome::Oscillating { .. })));
+        assert_eq!(watched, [(0, 0), (1, 2), (2, 0)]);
+
+        let mut sim = Simulation::new(&room, &Neighbourhood::adjacent());
+        assert_eq!(sim.run_watching(|_| Err("stop")), Err("stop"));
+        assert_eq!(sim.ticks(), 0);
+
+        // A row that starts part filled only falls into that flicker after a round
+        let late = WaitingRoom::parse("L##\n").unwrap();
+        let mut sim = Simulation::new(&late, &Neighbourhood::adjacent().with_tolerance(1));
+        assert_eq!(
+            sim.run(),
+            Outcome::Oscillating {
+                start: 1,
+                period: 2
+            }
+        );
+    }
+}
@@ -1,323 +1,137 @@
-use std::io::{self, Read};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::time::Duration;
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+use aoc11::render::{self, GifRecorder};
+use aoc11::{Neighbourhood, Simulation, WaitingRoom};
 
-static MOVE_DIRS: [(isize, isize); 8] = [
-    (-1, 1),
-    (0, 1),
-    (1, 1),
-    (-1, 0),
-    (1, 0),
-    (-1, -1),
-    (0, -1),
-    (1, -1),
-];
+const USAGE: &str = "Usage: aoc11 [--rule adjacent|sight] [--tolerance <n>] [--orthogonal] \
+    [--animate] [--delay <ms>] [--frames <dir> [--format pbm|ppm]] [--gif <file>] \
+    [--scale <px>] < input";
 
-#[derive(Debug)]
-enum Part {
-    P1,
-    P2,
+fn parse_number(arg: Option<&String>) -> aoc_core::Result<u64> {
+    Ok(arg.ok_or(USAGE)?.parse()?)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum SeatStatus {
-    FLOOR,
-    EMPTY,
-    OCCUPIED,
-}
-
-#[derive(Debug)]
-struct Seat {
-    status: SeatStatus,
-    neighbors: Vec<usize>,
-}
-
-#[derive(Debug)]
-struct WaitingRoom {
-    nrows: usize,
-    ncols: usize,
-    seats: Vec<Seat>,
-    curr_seat_status: Vec<SeatStatus>,
-}
-
-fn get_neighbors(nrows: usize, ncols: usize, idx: usize) -> Vec<usize> {
-    let mut neighbors = vec![];
-
-    let i = idx / ncols;
-    let j = idx % ncols;
-
-    for (mi, mj) in MOVE_DIRS.iter() {
-        let ni = i as isize + mi;
-        let nj = j as isize + mj;
-
-        if (ni < 0) | (ni >= nrows as isize) | (nj < 0) | (nj >= ncols as isize) {
-            continue;
-        }
-
-        let nidx = ni as usize * ncols + nj as usize;
-
-        neighbors.push(nidx);
-    }
-
-    neighbors
-}
-
-struct DirScanner {
-    idx: usize,
-    dix: usize,
-    m: usize,
-    nrows: usize,
-    ncols: usize,
-}
-
-impl DirScanner {
-    fn new(nrows: usize, ncols: usize, idx: usize, dix: usize) -> DirScanner {
-        DirScanner {
-            idx: idx,
-            dix: dix,
-            m: 1,
-            nrows: nrows,
-            ncols: ncols,
-        }
+/// With no arguments, solve both parts. Otherwise run one seating rule:
+/// `--rule` picks the adjacent seats (the default) or the first seat in
+/// sight, `--tolerance` how many taken neighbours make someone leave and
+/// `--orthogonal` only looks in four directions rather than eight.
+///
+/// Every round until the room settles, or until it has gone through a full
+/// cycle if it repeats, can be drawn as the simulation runs: `--animate` in the terminal, `--frames` as one
+/// PBM or PPM image per round in a directory, and `--gif` as an animation.
+/// `--delay` sets the time between rounds and `--scale` the pixels per seat.
+fn main() -> aoc_core::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if args.is_empty() {
+        return aoc_core::main::<aoc11::Day11>();
     }
-}
-
-impl Iterator for DirScanner {
-    type Item = usize;
 
-    fn next(&mut self) -> Option<usize> {
-        let i = self.idx / self.ncols;
-        let j = self.idx % self.ncols;
-
-        let mi = MOVE_DIRS[self.dix].0 * self.m as isize;
-        let mj = MOVE_DIRS[self.dix].1 * self.m as isize;
-
-        let ni = i as isize + mi;
-        let nj = j as isize + mj;
-
-        self.m += 1;
-
-        if (ni < 0) | (ni >= self.nrows as isize) | (nj < 0) | (nj >= self.ncols as isize) {
-            None
-        } else {
-            Some(ni as usize * self.ncols + nj as usize)
-        }
-    }
-}
-
-impl WaitingRoom {
-    fn from_input(input: &str) -> WaitingRoom {
-        let mut sid = 0;
-
-        // Get grid size
-        let nrows = input.lines().count();
-        let ncols = input
-            .lines()
-            .take(1)
-            .next()
-            .expect("could not extract first line")
-            .chars()
-            .count();
-
-        let mut seats = Vec::with_capacity(nrows * ncols);
-        let mut curr_seat_status = Vec::with_capacity(nrows * ncols);
-
-        for line in input.lines() {
-            for c in line.chars() {
-                let status = match c {
-                    '.' => SeatStatus::FLOOR,
-                    '#' => SeatStatus::OCCUPIED,
-                    'L' => SeatStatus::EMPTY,
-                    _ => unreachable!(),
-                };
-
-                seats.push(Seat {
-                    status: status.clone(),
-                    neighbors: get_neighbors(nrows, ncols, sid),
-                });
-
-                curr_seat_status.push(status.clone());
-                sid += 1;
-            }
-        }
-
-        WaitingRoom {
-            nrows,
-            ncols,
-            seats,
-            curr_seat_status,
-        }
-    }
-
-    fn print_grid(&self) {
-        print!(
-            "{}\n",
-            self.curr_seat_status
-                .chunks(self.ncols)
-                .map(|x| {
-                    x.iter()
-                        .map(|s| match s {
-                            SeatStatus::OCCUPIED => "#",
-                            SeatStatus::EMPTY => "L",
-                            SeatStatus::FLOOR => ".",
-                        })
-                        .collect::<Vec<_>>()
-                        .join("")
-                })
-                .collect::<Vec<_>>()
-                .join("\n")
-        );
-    }
-
-    fn get_num_visible_occupied(&self, idx: usize) -> i64 {
-        let mut num_visible_occ = 0;
-
-        for i in 0..8 {
-            let ds = DirScanner::new(self.nrows, self.ncols, idx, i);
-
-            for ni in ds {
-                match self.seats[ni].status {
-                    SeatStatus::OCCUPIED => {
-                        num_visible_occ += 1;
-                        break;
-                    }
-                    SeatStatus::EMPTY => break,
-                    SeatStatus::FLOOR => (),
+    let mut rule = Neighbourhood::adjacent();
+    let mut tolerance = None;
+    let mut orthogonal = false;
+    let mut animate = false;
+    let mut delay = Duration::from_millis(100);
+    let mut frames = None;
+    let mut ppm = true;
+    let mut gif = None;
+    let mut scale = 4;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--rule" => {
+                rule = match it.next().map(String::as_str) {
+                    Some("adjacent") => Neighbourhood::adjacent(),
+                    Some("sight") => Neighbourhood::line_of_sight(),
+                    _ => return Err(USAGE.into()),
                 }
-                if let SeatStatus::OCCUPIED = self.seats[ni].status {
-                    num_visible_occ += 1;
-                    break;
+            }
+            "--tolerance" => tolerance = Some(parse_number(it.next())? as usize),
+            "--orthogonal" => orthogonal = true,
+            "--animate" => animate = true,
+            "--delay" => delay = Duration::from_millis(parse_number(it.next())?),
+            "--frames" => frames = Some(PathBuf::from(it.next().ok_or(USAGE)?)),
+            "--format" => {
+                ppm = match it.next().map(String::as_str) {
+                    Some("pbm") => false,
+                    Some("ppm") => true,
+                    _ => return Err(USAGE.into()),
                 }
             }
+            "--gif" => gif = Some(PathBuf::from(it.next().ok_or(USAGE)?)),
+            "--scale" => scale = parse_number(it.next())?.max(1) as usize,
+            _ => return Err(USAGE.into()),
         }
+    }
 
-        num_visible_occ
+    if let Some(t) = tolerance {
+        rule = rule.with_tolerance(t);
+    }
+    if orthogonal {
+        rule = rule.orthogonal();
     }
 
-    fn get_seat_update_p1(&self, idx: usize) -> SeatStatus {
-        let seat = &self.seats[idx];
-        match seat.status {
-            SeatStatus::EMPTY => {
-                if seat
-                    .neighbors
-                    .iter()
-                    .all(|&x| self.seats[x].status != SeatStatus::OCCUPIED)
-                {
-                    SeatStatus::OCCUPIED
-                } else {
-                    SeatStatus::EMPTY
-                }
-            }
-            SeatStatus::FLOOR => SeatStatus::FLOOR,
-            SeatStatus::OCCUPIED => {
-                let num_occupied = seat
-                    .neighbors
-                    .iter()
-                    .filter(|&x| self.seats[*x].status == SeatStatus::OCCUPIED)
-                    .count();
-                if num_occupied >= 4 {
-                    SeatStatus::EMPTY
-                } else {
-                    SeatStatus::OCCUPIED
-                }
-            }
+    let input = aoc_core::input::read_stdin()?;
+    let room = WaitingRoom::parse(&input)?;
+    let mut sim = Simulation::new(&room, &rule);
+
+    let outcome = if animate || frames.is_some() || gif.is_some() {
+        // Every frame is drawn from the simulation, so the GIF takes its size
+        // from one of them
+        let seats = sim.room();
+        let mut recorder = match &gif {
+            Some(path) => Some(GifRecorder::new(
+                BufWriter::new(File::create(path)?),
+                seats.width(),
+                seats.height(),
+                scale,
+                delay,
+            )?),
+            None => None,
+        };
+        if let Some(dir) = &frames {
+            std::fs::create_dir_all(dir)?;
         }
-    }
 
-    fn get_seat_update_p2(&self, idx: usize) -> SeatStatus {
-        let seat = &self.seats[idx];
-        match seat.status {
-            SeatStatus::EMPTY => {
-                let noc = self.get_num_visible_occupied(idx);
-                if noc == 0 {
-                    SeatStatus::OCCUPIED
-                } else {
-                    SeatStatus::EMPTY
-                }
+        sim.run_watching(|sim| -> aoc_core::Result<()> {
+            let seats = sim.room();
+
+            if animate {
+                print!("\x1b[2J\x1b[H{}", render::ansi(&seats));
+                println!(
+                    "round {}: {} seats occupied",
+                    sim.ticks(),
+                    sim.num_occupied()
+                );
+                std::thread::sleep(delay);
             }
-            SeatStatus::FLOOR => SeatStatus::FLOOR,
-            SeatStatus::OCCUPIED => {
-                let num_occupied = self.get_num_visible_occupied(idx);
-                if num_occupied >= 5 {
-                    SeatStatus::EMPTY
-                } else {
-                    SeatStatus::OCCUPIED
+            if let Some(dir) = &frames {
+                let name = format!(
+                    "round-{:04}.{}",
+                    sim.ticks(),
+                    if ppm { "ppm" } else { "pbm" }
+                );
+                let mut out = BufWriter::new(File::create(dir.join(name))?);
+                match ppm {
+                    true => render::write_ppm(&mut out, &seats, scale)?,
+                    false => render::write_pbm(&mut out, &seats, scale)?,
                 }
             }
-        }
-    }
-
-    fn update(&mut self, part: Part) -> bool {
-        let mut room_changed = false;
-
-        for (si, seat) in self.seats.iter().enumerate() {
-            let new_status = match part {
-                Part::P1 => self.get_seat_update_p1(si),
-                Part::P2 => self.get_seat_update_p2(si),
-            };
-            if new_status != seat.status {
-                room_changed = true;
-                self.curr_seat_status[si] = new_status;
-            }
-        }
-
-        if room_changed {
-            for (seat, status) in self.seats.iter_mut().zip(self.curr_seat_status.iter()) {
-                seat.status = *status;
+            if let Some(recorder) = &mut recorder {
+                recorder.push(&seats)?;
             }
-        }
-
-        room_changed
-    }
-}
-
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
-
-    let start = std::time::Instant::now();
-    part1(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
-
-    let start = std::time::Instant::now();
-    part2(&input)?;
-    eprintln!("elapsed {:?}", start.elapsed());
-
-    Ok(())
-}
-
-fn part1(input: &str) -> Result<()> {
-    let mut room = WaitingRoom::from_input(input);
-
-    while room.update(Part::P1) {
-        continue;
-    }
-
-    let soln = room
-        .seats
-        .iter()
-        .filter(|x| x.status == SeatStatus::OCCUPIED)
-        .count();
-
-    println!("part 1 solution: {}", soln);
-
-    Ok(())
-}
-
-fn part2(input: &str) -> Result<()> {
-    let mut room = WaitingRoom::from_input(input);
-
-    while room.update(Part::P2) {
-        continue;
-    }
 
-    let soln = room
-        .seats
-        .iter()
-        .filter(|x| x.status == SeatStatus::OCCUPIED)
-        .count();
+            Ok(())
+        })?
+    } else {
+        sim.run()
+    };
 
-    println!("part 2 solution: {}", soln);
+    println!("{}", outcome);
 
     Ok(())
 }
@@ -0,0 +1,196 @@
+use std::convert::TryFrom;
+use std::io::Write;
+use std::time::Duration;
+
+use gif::{Encoder, Frame, Repeat};
+
+use aoc_core::grid::Grid;
+use aoc_core::Result;
+
+use crate::SeatStatus;
+
+/// The colour of each kind of cell, as RGB, in the order of `palette_index`
+const PALETTE: [[u8; 3]; 3] = [
+    [0x20, 0x20, 0x20], // floor
+    [0x2e, 0xa0, 0x43], // empty
+    [0xd6, 0x3e, 0x3e], // occupied
+];
+
+fn palette_index(seat: SeatStatus) -> u8 {
+    match seat {
+        SeatStatus::Floor => 0,
+        SeatStatus::Empty => 1,
+        SeatStatus::Occupied => 2,
+    }
+}
+
+/// Draw the room for a terminal: taken seats in red, free ones in green and
+/// the floor dimmed
+pub fn ansi(seats: &Grid<SeatStatus>) -> String {
+    let mut out = String::new();
+
+    for row in seats.rows() {
+        for &seat in row {
+            let colour = match seat {
+                SeatStatus::Floor => "0;2",
+                SeatStatus::Empty => "0;32",
+                SeatStatus::Occupied => "0;1;31",
+            };
+            out.push_str(&format!("\x1b[{}m{}", colour, seat));
+        }
+        out.push_str("\x1b[0m\n");
+    }
+
+    out
+}
+
+/// Every pixel of the room drawn `scale` times larger, row by row
+fn pixels(seats: &Grid<SeatStatus>, scale: usize) -> impl Iterator<Item = Vec<SeatStatus>> + '_ {
+    seats.rows().flat_map(move |row| {
+        let line: Vec<SeatStatus> = row
+            .iter()
+            .flat_map(|&seat| std::iter::repeat_n(seat, scale))
+            .collect();
+        std::iter::repeat_n(line, scale)
+    })
+}
+
+/// Write the room as a binary PBM, black where a seat is taken
+pub fn write_pbm<W: Write>(out: &mut W, seats: &Grid<SeatStatus>, scale: usize) -> Result<()> {
+    writeln!(
+        out,
+        "P4\n{} {}",
+        seats.width() * scale,
+        seats.height() * scale
+    )?;
+
+    for line in pixels(seats, scale) {
+        let bytes: Vec<u8> = line
+            .chunks(8)
+            .map(|bits| {
+                bits.iter().enumerate().fold(0, |byte, (i, &seat)| {
+                    byte | ((seat == SeatStatus::Occupied) as u8) << (7 - i)
+                })
+            })
+            .collect();
+        out.write_all(&bytes)?;
+    }
+
+    Ok(())
+}
+
+/// Write the room as a binary PPM in the terminal's colours
+pub fn write_ppm<W: Write>(out: &mut W, seats: &Grid<SeatStatus>, scale: usize) -> Result<()> {
+    writeln!(
+        out,
+        "P6\n{} {}\n255",
+        seats.width() * scale,
+        seats.height() * scale
+    )?;
+
+    for line in pixels(seats, scale) {
+        for seat in line {
+            out.write_all(&PALETTE[palette_index(seat) as usize])?;
+        }
+    }
+
+    Ok(())
+}
+
+/// An animated GIF of a simulation, one frame per round, looping forever
+pub struct GifRecorder<W: Write> {
+    encoder: Encoder<W>,
+    width: u16,
+    height: u16,
+    scale: usize,
+    /// Between frames, in hundredths of a second
+    delay: u16,
+}
+
+impl<W: Write> GifRecorder<W> {
+    /// Record frames of a `width` by `height` room, each cell `scale` pixels
+    /// square, showing each round for `delay`
+    pub fn new(
+        out: W,
+        width: usize,
+        height: usize,
+        scale: usize,
+        delay: Duration,
+    ) -> Result<GifRecorder<W>> {
+        let too_big = || format!("a {}x{} room is too big for a GIF", width, height);
+        let width = u16::try_from(width * scale).map_err(|_| too_big())?;
+        let height = u16::try_from(height * scale).map_err(|_| too_big())?;
+
+        let mut encoder = Encoder::new(out, width, height, PALETTE.concat().as_slice())?;
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        Ok(GifRecorder {
+            encoder,
+            width,
+            height,
+            scale,
+            delay: (delay.as_millis() / 10).min(u16::MAX as u128) as u16,
+        })
+    }
+
+    pub fn push(&mut self, seats: &Grid<SeatStatus>) -> Result<()> {
+        let indices: Vec<u8> = pixels(seats, self.scale)
+            .flatten()
+            .map(palette_index)
+            .collect();
+        if indices.len() != self.width as usize * self.height as usize {
+            return Err("every frame must be the same size".into());
+        }
+
+        let mut frame = Frame::from_indexed_pixels(self.width, self.height, indices, None);
+        frame.delay = self.delay;
+        self.encoder.write_frame(&frame)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Neighbourhood, Simulation, WaitingRoom};
+
+    #[test]
+    fn image_test() {
+        let room = WaitingRoom::parse("#.L#.L#.L#\n##########\n").unwrap();
+        let mut pbm = vec![];
+        write_pbm(&mut pbm, room.seats(), 1).unwrap();
+        assert_eq!(pbm, b"P4\n10 2\n\x92\x40\xff\xc0");
+
+        let mut ppm = vec![];
+        write_ppm(&mut ppm, room.seats(), 2).unwrap();
+        assert_eq!(ppm.len(), "P6\n20 4\n255\n".len() + 20 * 4 * 3);
+
+        let mut gif = vec![];
+        let mut recorder = GifRecorder::new(&mut gif, 10, 2, 3, Duration::from_millis(50)).unwrap();
+        recorder.push(room.seats()).unwrap();
+        assert!(recorder
+            .push(&Grid::filled(2, 2, SeatStatus::Floor))
+            .is_err());
+        drop(recorder);
+
+        let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (30, 6));
+        let frame = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(frame.delay, 5);
+        assert!(decoder.read_next_frame().unwrap().is_none());
+
+        // A last row of nothing but floor is still drawn
+        let room = WaitingRoom::parse("L.L\n...\n").unwrap();
+        let mut sim = Simulation::new(&room, &Neighbourhood::adjacent());
+        let mut recorder = GifRecorder::new(vec![], 3, 2, 4, Duration::from_millis(50)).unwrap();
+        recorder.push(&sim.room()).unwrap();
+        sim.tick();
+        recorder.push(&sim.room()).unwrap();
+
+        let mut pbm = vec![];
+        write_pbm(&mut pbm, &sim.room(), 4).unwrap();
+        assert!(pbm.starts_with(b"P4\n12 8\n"));
+        assert_eq!(pbm.len(), "P4\n12 8\n".len() + 8 * 2);
+    }
+}
@@ -0,0 +1,373 @@
+use std::fmt;
+
+use aoc_core::parse::parse_at;
+use aoc_core::{ParseError, Result, Solution};
+
+pub mod trajectory;
+pub use trajectory::Trajectory;
+
+/// A position or a displacement, with east and north positive. Moves are
+/// checked, since a long enough voyage leaves even this range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self) -> Option<i64> {
+        self.x.checked_abs()?.checked_add(self.y.checked_abs()?)
+    }
+
+    pub fn checked_add(self, other: Point) -> Option<Point> {
+        Some(Point::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+        ))
+    }
+
+    pub fn checked_sub(self, other: Point) -> Option<Point> {
+        Some(Point::new(
+            self.x.checked_sub(other.x)?,
+            self.y.checked_sub(other.y)?,
+        ))
+    }
+
+    pub fn checked_mul(self, n: i64) -> Option<Point> {
+        Some(Point::new(self.x.checked_mul(n)?, self.y.checked_mul(n)?))
+    }
+
+    /// Turned about the origin by `quarters` clockwise right angles, or
+    /// anticlockwise ones if negative
+    pub fn checked_rotate(self, quarters: i32) -> Option<Point> {
+        let Point { x, y } = self;
+
+        match quarters.rem_euclid(4) {
+            0 => Some(Point::new(x, y)),
+            1 => Some(Point::new(y, x.checked_neg()?)),
+            2 => Some(Point::new(x.checked_neg()?, y.checked_neg()?)),
+            _ => Some(Point::new(y.checked_neg()?, x)),
+        }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heading {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Heading {
+    /// One unit in this direction
+    pub fn vector(self) -> Point {
+        match self {
+            Heading::North => Point::new(0, 1),
+            Heading::East => Point::new(1, 0),
+            Heading::South => Point::new(0, -1),
+            Heading::West => Point::new(-1, 0),
+        }
+    }
+
+    fn letter(self) -> char {
+        match self {
+            Heading::North => 'N',
+            Heading::East => 'E',
+            Heading::South => 'S',
+            Heading::West => 'W',
+        }
+    }
+}
+
+/// One line of the navigation instructions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavInstruction {
+    /// `N`, `E`, `S` or `W`: move by this much in a fixed direction
+    Shift(Heading, i32),
+    /// `R` or `L`: turn by this many clockwise quarter turns, or
+    /// anticlockwise ones if negative
+    Turn(i32),
+    /// `F`: move forward this many times
+    Forward(i32),
+}
+
+impl NavInstruction {
+    /// A clockwise turn by `degrees`, anticlockwise if negative, which must be
+    /// a whole number of right angles
+    pub fn turn(degrees: i32) -> Result<NavInstruction> {
+        if degrees % 90 != 0 {
+            return Err(format!(
+                "cannot turn by {} degrees, only by multiples of 90",
+                degrees
+            )
+            .into());
+        }
+
+        Ok(NavInstruction::Turn(degrees / 90))
+    }
+
+    /// Parse an instruction like `F10` or `L270`, `line` being a slice of
+    /// `input`
+    pub fn parse(input: &str, line: &str) -> Result<NavInstruction> {
+        let split = line
+            .char_indices()
+            .nth(1)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        let (action, num) = line.split_at(split);
+        let value: i32 = parse_at(input, num, "a number")?;
+
+        let instruction = match action {
+            "N" => NavInstruction::Shift(Heading::North, value),
+            "E" => NavInstruction::Shift(Heading::East, value),
+            "S" => NavInstruction::Shift(Heading::South, value),
+            "W" => NavInstruction::Shift(Heading::West, value),
+            "F" => NavInstruction::Forward(value),
+            "R" | "L" => {
+                // Only `i32::MIN` has no negative, and it is no multiple of 90
+                let degrees = if action == "R" {
+                    Some(value)
+                } else {
+                    value.checked_neg()
+                };
+                degrees
+                    .and_then(|d| NavInstruction::turn(d).ok())
+                    .ok_or_else(|| ParseError::at(input, num, "a multiple of 90 degrees"))?
+            }
+            _ => {
+                let expected = "one of `N`, `S`, `E`, `W`, `L`, `R` or `F`";
+                return Err(ParseError::at(input, action, expected).into());
+            }
+        };
+
+        Ok(instruction)
+    }
+}
+
+impl fmt::Display for NavInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            NavInstruction::Shift(heading, n) => write!(f, "{}{}", heading.letter(), n),
+            NavInstruction::Turn(q) if q < 0 => write!(f, "L{}", -(q as i64) * 90),
+            NavInstruction::Turn(q) => write!(f, "R{}", q as i64 * 90),
+            NavInstruction::Forward(n) => write!(f, "F{}", n),
+        }
+    }
+}
+
+pub fn parse_instructions(input: &str) -> Result<Vec<NavInstruction>> {
+    input
+        .lines()
+        .map(|line| NavInstruction::parse(input, line))
+        .collect()
+}
+
+/// Something that steers the ship by the navigation instructions
+pub trait Navigator {
+    /// Where the ship is
+    fn position(&self) -> Point;
+
+    /// Carry out one instruction, failing if it takes the ship or its
+    /// waypoint out of range
+    fn execute(&mut self, instruction: NavInstruction) -> Result<()>;
+
+    /// Carry out every instruction in turn, recording where the ship goes
+    fn navigate(&mut self, instructions: &[NavInstruction]) -> Result<Trajectory> {
+        let mut trajectory = Trajectory::new(self.position());
+
+        for (i, &instruction) in instructions.iter().enumerate() {
+            self.execute(instruction)
+                .map_err(|e| format!("instruction {}: {}", i + 1, e))?;
+            trajectory.push(instruction, self.position());
+        }
+
+        Ok(trajectory)
+    }
+}
+
+/// `from` moved `n` times by `step`
+fn shift(from: Point, step: Point, n: i32, instruction: NavInstruction) -> Result<Point> {
+    step.checked_mul(n as i64)
+        .and_then(|d| from.checked_add(d))
+        .ok_or_else(|| out_of_range(instruction))
+}
+
+fn out_of_range(instruction: NavInstruction) -> aoc_core::Error {
+    format!("`{}` goes further than can be tracked", instruction).into()
+}
+
+/// The reading of part 1: `N`, `E`, `S` and `W` move the ship itself, and
+/// turns change the way it faces
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShipNavigator {
+    pub position: Point,
+    /// A unit vector the way the ship faces
+    pub heading: Point,
+}
+
+impl ShipNavigator {
+    /// At the origin, facing east
+    pub fn new() -> ShipNavigator {
+        ShipNavigator {
+            position: Point::default(),
+            heading: Heading::East.vector(),
+        }
+    }
+}
+
+impl Default for ShipNavigator {
+    fn default() -> ShipNavigator {
+        ShipNavigator::new()
+    }
+}
+
+impl Navigator for ShipNavigator {
+    fn position(&self) -> Point {
+        self.position
+    }
+
+    fn execute(&mut self, instruction: NavInstruction) -> Result<()> {
+        match instruction {
+            NavInstruction::Shift(heading, n) => {
+                self.position = shift(self.position, heading.vector(), n, instruction)?
+            }
+            NavInstruction::Turn(q) => {
+                self.heading = self
+                    .heading
+                    .checked_rotate(q)
+                    .ok_or_else(|| out_of_range(instruction))?
+            }
+            NavInstruction::Forward(n) => {
+                self.position = shift(self.position, self.heading, n, instruction)?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The reading of part 2: `N`, `E`, `S` and `W` move a waypoint relative
+/// to the ship, turns swing it around the ship, and the ship moves towards it
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaypointNavigator {
+    pub position: Point,
+    /// Relative to the ship
+    pub waypoint: Point,
+}
+
+impl WaypointNavigator {
+    /// At the origin, with the waypoint 10 east and 1 north
+    pub fn new() -> WaypointNavigator {
+        WaypointNavigator {
+            position: Point::default(),
+            waypoint: Point::new(10, 1),
+        }
+    }
+}
+
+impl Default for WaypointNavigator {
+    fn default() -> WaypointNavigator {
+        WaypointNavigator::new()
+    }
+}
+
+impl Navigator for WaypointNavigator {
+    fn position(&self) -> Point {
+        self.position
+    }
+
+    fn execute(&mut self, instruction: NavInstruction) -> Result<()> {
+        match instruction {
+            NavInstruction::Shift(heading, n) => {
+                self.waypoint = shift(self.waypoint, heading.vector(), n, instruction)?
+            }
+            NavInstruction::Turn(q) => {
+                self.waypoint = self
+                    .waypoint
+                    .checked_rotate(q)
+                    .ok_or_else(|| out_of_range(instruction))?
+            }
+            NavInstruction::Forward(n) => {
+                self.position = shift(self.position, self.waypoint, n, instruction)?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = Vec<NavInstruction>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<NavInstruction>> {
+        parse_instructions(input)
+    }
+
+    fn part1(instructions: &Vec<NavInstruction>) -> Result<i64> {
+        ShipNavigator::new().navigate(instructions)?.distance()
+    }
+
+    fn part2(instructions: &Vec<NavInstruction>) -> Result<i64> {
+        WaypointNavigator::new().navigate(instructions)?.distance()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn navigator_test() {
+        let input = "F10\nN3\nF7\nR90\nF11\n";
+        let instructions = parse_instructions(input).unwrap();
+        let ship = ShipNavigator::new().navigate(&instructions).unwrap();
+        assert_eq!(ship.distance().unwrap(), 25);
+        let waypoint = WaypointNavigator::new().navigate(&instructions).unwrap();
+        assert_eq!(waypoint.distance().unwrap(), 286);
+
+        // Any whole number of right angles, either way round
+        let turns = parse_instructions("R450\nL270\nL90\nR0\n").unwrap();
+        assert_eq!(turns, [5, -3, -1, 0].map(NavInstruction::Turn));
+        assert_eq!(turns[1].to_string(), "L270");
+
+        let mut ship = ShipNavigator::new();
+        for &turn in &turns[..2] {
+            ship.execute(turn).unwrap();
+        }
+        assert_eq!(ship.heading, Heading::West.vector());
+
+        let e = parse_instructions("F10\nL45\n").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 2: expected a multiple of 90 degrees, found `45`"
+        );
+        assert!(NavInstruction::turn(-135).is_err());
+        assert!(parse_instructions("L-2147483648\n").is_err());
+
+        // Far enough out, the next move cannot be counted
+        let mut ship = WaypointNavigator::new();
+        ship.waypoint = Point::new(i64::MAX / 2, 0);
+        let e = ship
+            .navigate(&parse_instructions("F1\nF2\n").unwrap())
+            .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "instruction 2: `F2` goes further than can be tracked"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use aoc_core::Result;
+
+use crate::{NavInstruction, Point};
+
+/// Everywhere a ship has been: where it started and where each instruction
+/// took it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trajectory {
+    start: Point,
+    legs: Vec<(NavInstruction, Point)>,
+}
+
+impl Trajectory {
+    pub fn new(start: Point) -> Trajectory {
+        Trajectory {
+            start,
+            legs: vec![],
+        }
+    }
+
+    pub fn push(&mut self, instruction: NavInstruction, to: Point) {
+        self.legs.push((instruction, to));
+    }
+
+    pub fn start(&self) -> Point {
+        self.start
+    }
+
+    pub fn end(&self) -> Point {
+        self.legs.last().map_or(self.start, |&(_, p)| p)
+    }
+
+    /// The Manhattan distance from start to end
+    pub fn distance(&self) -> Result<i64> {
+        self.end()
+            .checked_sub(self.start)
+            .and_then(Point::manhattan)
+            .ok_or_else(|| "the ship went too far to measure".into())
+    }
+
+    /// The start, then the position after each instruction
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        std::iter::once(self.start).chain(self.legs.iter().map(|&(_, p)| p))
+    }
+
+    /// The smallest and largest corners of a box around every point
+    pub fn bounds(&self) -> (Point, Point) {
+        self.points().fold((self.start, self.start), |(lo, hi), p| {
+            (
+                Point::new(lo.x.min(p.x), lo.y.min(p.y)),
+                Point::new(hi.x.max(p.x), hi.y.max(p.y)),
+            )
+        })
+    }
+
+    /// One row per position, with the instruction that led there
+    pub fn to_csv(&self) -> String {
+        let mut out = format!(
+            "step,instruction,x,y\n0,,{},{}\n",
+            self.start.x, self.start.y
+        );
+
+        for (i, (instruction, p)) in self.legs.iter().enumerate() {
+            out.push_str(&format!("{},{},{},{}\n", i + 1, instruction, p.x, p.y));
+        }
+
+        out
+    }
+
+    /// The route as an SVG path with north up, marking the start in green and
+    /// the end in red
+    pub fn to_svg(&self) -> String {
+        // Widened, so that flipping the y axis and adding margins cannot
+        // overflow anywhere a checked move can reach
+        let (lo, hi) = self.bounds();
+        let (lo_x, lo_y, hi_x, hi_y) = (lo.x as i128, lo.y as i128, hi.x as i128, hi.y as i128);
+        let size = (hi_x - lo_x).max(hi_y - lo_y).max(1);
+        let margin = size / 20 + 1;
+        let radius = size / 100 + 1;
+
+        // SVG's y axis points down
+        let path: Vec<String> = self
+            .points()
+            .enumerate()
+            .map(|(i, p)| {
+                let command = if i == 0 { 'M' } else { 'L' };
+                format!("{}{} {}", command, p.x, -(p.y as i128))
+            })
+            .collect();
+        let (start, end) = (self.start, self.end());
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n\
+             \x20 <path d=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"1\" \
+             vector-effect=\"non-scaling-stroke\"/>\n\
+             \x20 <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"green\"/>\n\
+             \x20 <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"red\"/>\n\
+             </svg>\n",
+            lo_x - margin,
+            -hi_y - margin,
+            hi_x - lo_x + 2 * margin,
+            hi_y - lo_y + 2 * margin,
+            path.join(" "),
+            start.x,
+            -(start.y as i128),
+            radius,
+            end.x,
+            -(end.y as i128),
+            radius,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_instructions, Navigator, ShipNavigator};
+
+    #[test]
+    fn export_test() {
+        let instructions = parse_instructions("F10\nN3\nF7\nR90\nF11\n").unwrap();
+        let trajectory = ShipNavigator::new().navigate(&instructions).unwrap();
+
+        assert_eq!(trajectory.end(), Point::new(17, -8));
+        assert_eq!(trajectory.bounds(), (Point::new(0, -8), Point::new(17, 3)));
+        assert_eq!(
+            trajectory.to_csv(),
+            "step,instruction,x,y\n0,,0,0\n1,F10,10,0\n2,N3,10,3\n3,F7,17,3\n4,R90,17,3\n5,F11,17,-8\n"
+        );
+
+        let svg = trajectory.to_svg();
+        assert!(svg.contains("viewBox=\"-1 -4 19 13\""));
+        assert!(svg.contains("d=\"M0 0 L10 0 L10 -3 L17 -3 L17 -3 L17 8\""));
+    }
+}
@@ -0,0 +1,111 @@
+use aoc_core::parse::parse_at;
+use aoc_core::{ParseError, Result, Solution};
+
+fn parse_input(input: &str) -> Result<(i32, Vec<i32>)> {
+    let mut it = input.lines();
+
+    let target = it
+        .next()
+        .ok_or_else(|| ParseError::at_end(input, "a departure time"))?;
+    let target = parse_at(input, target, "a departure time")?;
+
+    let sched = it
+        .next()
+        .ok_or_else(|| ParseError::at_end(input, "a bus schedule"))?
+        .split(',')
+        .map(|x| match x {
+            "x" => Ok(-1),
+            _ => parse_at(input, x, "a bus id or `x`"),
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok((target, sched))
+}
+
+fn scan(sched: &[i32]) -> i64 {
+    let mut time: i64 = 0;
+    let mut step: i64 = 1;
+
+    for (offset, bus_id) in sched.iter().map(|&x| x as i64).enumerate() {
+        if bus_id == -1 {
+            continue;
+        }
+
+        while (time + offset as i64) % bus_id != 0 {
+            time += step;
+        }
+
+        step *= bus_id;
+    }
+
+    time
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = (i32, Vec<i32>);
+    type Part1 = i32;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<(i32, Vec<i32>)> {
+        parse_input(input)
+    }
+
+    fn part1((target, sched): &(i32, Vec<i32>)) -> Result<i32> {
+        let target = *target;
+        let mut best = i32::MAX;
+        let mut best_id = None;
+
+        for &v in sched {
+            if v == -1 {
+                continue;
+            }
+
+            if target % v == 0 {
+                best = target;
+                best_id = Some(v);
+                break;
+            }
+
+            let x = (target as f32 / v as f32).ceil() as i32 * v;
+
+            if x < best {
+                best = x;
+                best_id = Some(v);
+            }
+        }
+
+        let bus_id = best_id.ok_or("No solution found")?;
+        Ok(bus_id * (best - target))
+    }
+
+    fn part2((_, sched): &(i32, Vec<i32>)) -> Result<i64> {
+        let soln = scan(sched);
+
+        Ok(soln)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part2_test() {
+        let sched = vec![17, -1, 13, 19];
+        assert!(scan(&sched) == 3417);
+
+        let sched = vec![67, 7, 59, 61];
+        assert!(scan(&sched) == 754018);
+
+        let sched = vec![67, -1, 7, 59, 61];
+        assert!(scan(&sched) == 779210);
+
+        let sched = vec![67, 7, -1, 59, 61];
+        assert!(scan(&sched) == 1261476);
+
+        let sched = vec![1789, 37, 47, 1889];
+        assert!(scan(&sched) == 1202161486);
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use aoc_core::parse::{parse_at, split_once_at};
+use aoc_core::{ParseError, Result, Solution};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = &'a str;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<&str> {
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Result<u64> {
+        part1(input)
+    }
+
+    fn part2(input: &&str) -> Result<u64> {
+        part2(input)
+    }
+}
+
+#[derive(Debug)]
+struct ProgramInstruction {
+    loc: usize,
+    value: u64,
+    set_mask: u64,
+    clear_mask: u64,
+}
+
+#[derive(Debug)]
+struct ProgramInstructionV2 {
+    loc: usize,
+    value: u64,
+    set_mask: u64,
+    floating: Vec<u64>,
+}
+
+/// The 36 characters after `mask = `
+fn parse_mask<'a>(input: &str, line: &'a str) -> Result<&'a str> {
+    let (_, mask) = split_once_at(input, line, " = ")?;
+
+    if let Some((i, c)) = mask
+        .char_indices()
+        .find(|(_, c)| !matches!(c, '0' | '1' | 'X'))
+    {
+        let found = &mask[i..i + c.len_utf8()];
+        return Err(ParseError::at(input, found, "`0`, `1` or `X`").into());
+    }
+
+    if mask.len() != 36 {
+        return Err(ParseError::at(input, mask, "a 36 bit mask").into());
+    }
+
+    Ok(mask)
+}
+
+/// The address and value of `mem[8] = 11`
+fn parse_mem(input: &str, line: &str) -> Result<(usize, u64)> {
+    let (lhs, value) = split_once_at(input, line, " = ")?;
+    let loc = lhs
+        .strip_prefix("mem[")
+        .and_then(|l| l.strip_suffix(']'))
+        .ok_or_else(|| ParseError::at(input, lhs, "`mem[<address>]`"))?;
+
+    Ok((
+        parse_at(input, loc, "an address")?,
+        parse_at(input, value, "a number")?,
+    ))
+}
+
+fn parse_input(input: &str) -> Result<Vec<ProgramInstruction>> {
+    let mut pi = vec![];
+
+    let mut curr_set_mask: u64 = 0;
+    let mut curr_clear_mask: u64 = u64::MAX;
+
+    for line in input.lines() {
+        if line.starts_with("mask") {
+            let mask_str = parse_mask(input, line)?;
+
+            curr_set_mask = mask_str
+                .chars()
+                .rev()
+                .enumerate()
+                .filter(|(_, c)| *c == '1')
+                .fold(0, |acc, (i, _)| acc | (1 << i));
+
+            curr_clear_mask = mask_str
+                .chars()
+                .rev()
+                .enumerate()
+                .filter(|(_, c)| *c == '0')
+                .fold(u64::MAX, |acc, (i, _)| acc & !(1 << i));
+        } else if line.starts_with("mem") {
+            let (loc, value) = parse_mem(input, line)?;
+
+            pi.push(ProgramInstruction {
+                loc,
+                value,
+                set_mask: curr_set_mask,
+                clear_mask: curr_clear_mask,
+            });
+        } else {
+            return Err(ParseError::at(input, line, "`mask` or `mem`").into());
+        }
+    }
+
+    Ok(pi)
+}
+
+fn parse_input_p2(input: &str) -> Result<Vec<ProgramInstructionV2>> {
+    let mut pi = vec![];
+
+    let mut curr_set_mask: u64 = 0;
+    let mut curr_floating: Vec<u64> = Vec::with_capacity(36);
+
+    for line in input.lines() {
+        if line.starts_with("mask") {
+            let mask_str = parse_mask(input, line)?;
+
+            curr_set_mask = mask_str
+                .chars()
+                .rev()
+                .enumerate()
+                .filter(|(_, c)| *c == '1')
+                .fold(0, |acc, (i, _)| acc | (1 << i));
+
+            let floating_mask: u64 = mask_str
+                .chars()
+                .rev()
+                .enumerate()
+                .filter(|(_, c)| *c == 'X')
+                .fold(0, |acc, (i, _)| acc | (1 << i));
+
+            curr_floating.clear();
+            for i in 0..36 {
+                if (floating_mask & (1 << i)) != 0 {
+                    curr_floating.push(i);
+                }
+            }
+        } else if line.starts_with("mem") {
+            let (loc, value) = parse_mem(input, line)?;
+
+            pi.push(ProgramInstructionV2 {
+                loc,
+                value,
+                set_mask: curr_set_mask,
+                floating: curr_floating.clone(),
+            });
+        } else {
+            return Err(ParseError::at(input, line, "`mask` or `mem`").into());
+        }
+    }
+
+    Ok(pi)
+}
+
+pub fn part1(input: &str) -> Result<u64> {
+    let instructions = parse_input(input)?;
+    let mut dockmem: HashMap<usize, u64> = HashMap::new();
+
+    for instr in instructions {
+        let mut value = instr.value;
+        value |= instr.set_mask;
+        value &= instr.clear_mask;
+
+        let e = dockmem.entry(instr.loc).or_insert(0);
+        *e = value;
+    }
+
+    let soln: u64 = dockmem.values().sum();
+    Ok(soln)
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    let instructions = parse_input_p2(input)?;
+    let mut dockmem: HashMap<usize, u64> = HashMap::new();
+
+    for instr in instructions {
+        let n_write = 2usize.pow(instr.floating.len() as u32);
+
+        for wix in 0..n_write {
+            let mut target_loc = instr.loc as u64 | instr.set_mask;
+
+            for ix in 0..instr.floating.len() {
+                if wix & (1 << ix) != 0 {
+                    target_loc |= 1 << instr.floating[ix];
+                } else {
+                    target_loc &= !(1 << instr.floating[ix]);
+                }
+            }
+
+            let e = dockmem.entry(target_loc as usize).or_insert(0);
+            *e = instr.value;
+        }
+    }
+
+    let soln: u64 = dockmem.values().sum();
+    Ok(soln)
+}
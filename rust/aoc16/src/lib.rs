@@ -0,0 +1,223 @@
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use aoc_core::parse::parse_at;
+use aoc_core::{ParseError, Result, Solution};
+type Range = std::ops::Range<u16>;
+
+#[derive(Debug)]
+enum InputSection {
+    Rules,
+    MyTicket,
+    NearbyTicket,
+}
+
+impl InputSection {
+    fn next(&self) -> InputSection {
+        match self {
+            InputSection::Rules => InputSection::MyTicket,
+            InputSection::MyTicket => InputSection::NearbyTicket,
+            InputSection::NearbyTicket => InputSection::Rules,
+        }
+    }
+}
+
+fn parse_range(input: &str, x: &Captures, name: &str) -> Result<u16> {
+    let y = x.name(name).ok_or(format!("no {}", name))?.as_str();
+
+    Ok(parse_at(input, y, "a number up to 65535")?)
+}
+
+#[derive(Debug, Default)]
+pub struct Notes {
+    rules: HashMap<String, Vec<Range>>,
+    my_ticket: Vec<u16>,
+    nearby_tickets: Vec<Vec<u16>>,
+}
+
+impl Notes {
+    fn parse_rule(input: &str, line: &str) -> Result<(String, Vec<Range>)> {
+        lazy_static! {
+            static ref RE_RULES: Regex = Regex::new(
+                r"(?P<rule_name>^.*): (?P<n1>\d+)-(?P<n2>\d+) or (?P<n3>\d+)-(?P<n4>\d+)"
+            )
+            .unwrap();
+        }
+
+        if let Some(x) = RE_RULES.captures(line) {
+            let r: Vec<Range> = vec![
+                Range {
+                    start: parse_range(input, &x, "n1")?,
+                    end: parse_range(input, &x, "n2")? + 1,
+                },
+                Range {
+                    start: parse_range(input, &x, "n3")?,
+                    end: parse_range(input, &x, "n4")? + 1,
+                },
+            ];
+
+            let rule_name = x
+                .name("rule_name")
+                .ok_or("no rule name")?
+                .as_str()
+                .to_string();
+
+            return Ok((rule_name, r));
+        }
+
+        Err(ParseError::at(input, line, "a rule like `class: 1-3 or 5-7`").into())
+    }
+
+    fn parse_ticket(input: &str, line: &str) -> Result<Vec<u16>> {
+        let mut t = vec![];
+        for x in line.split(',') {
+            t.push(parse_at(input, x, "a ticket number")?);
+        }
+
+        Ok(t)
+    }
+
+    fn from_input(input: &str) -> Result<Notes> {
+        let mut notes = Notes::default();
+
+        let mut section = InputSection::Rules;
+
+        for line in input.lines() {
+            if line.is_empty() {
+                section = section.next();
+                continue;
+            };
+
+            match section {
+                InputSection::Rules => {
+                    let (rule_name, ranges) = Notes::parse_rule(input, line)?;
+                    notes.rules.insert(rule_name, ranges);
+                }
+                InputSection::MyTicket => {
+                    if line != "your ticket:" {
+                        notes.my_ticket = Notes::parse_ticket(input, line)?;
+                    }
+                }
+                InputSection::NearbyTicket => {
+                    if line != "nearby tickets:" {
+                        notes.nearby_tickets.push(Notes::parse_ticket(input, line)?);
+                    }
+                }
+            }
+        }
+
+        Ok(notes)
+    }
+
+    fn is_num_valid(&self, x: &u16) -> bool {
+        for rule in self.rules.values().flatten() {
+            if rule.contains(x) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn is_ticket_valid(&self, ticket: &[u16]) -> bool {
+        for n in ticket {
+            if !self.is_num_valid(n) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = Notes;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Notes> {
+        Notes::from_input(input)
+    }
+
+    fn part1(notes: &Notes) -> Result<u32> {
+        let error_rate: u32 = notes
+            .nearby_tickets
+            .iter()
+            .flatten()
+            .map(|x| match notes.is_num_valid(x) {
+                true => 0,
+                false => *x as u32,
+            })
+            .sum();
+
+        Ok(error_rate)
+    }
+
+    fn part2(notes: &Notes) -> Result<u64> {
+        let valid_tickets: Vec<&Vec<u16>> = notes
+            .nearby_tickets
+            .iter()
+            .filter(|t| notes.is_ticket_valid(t))
+            .collect();
+
+        let num_fields = notes.my_ticket.len();
+        let mut field_candidates: Vec<HashSet<String>> = Vec::new();
+
+        for _ in 0..num_fields {
+            field_candidates.push(HashSet::new());
+        }
+
+        for i in 0..num_fields {
+            let field_values: Vec<u16> = valid_tickets.iter().map(|x| x[i]).collect();
+
+            for (rule_name, rules) in &notes.rules {
+                let mut rule_valid = true;
+
+                for fv in field_values.iter() {
+                    if !rules.iter().any(|v| v.contains(fv)) {
+                        rule_valid = false;
+                        break;
+                    }
+                }
+
+                if rule_valid {
+                    field_candidates[i].insert(rule_name.to_owned());
+                }
+            }
+        }
+
+        let mut field2col = HashMap::new();
+
+        // Scan through candidates for columns with only one candidate
+        // and then remove from all others
+        while field2col.len() < num_fields {
+            for field in field2col.keys() {
+                for candidate in field_candidates.iter_mut() {
+                    candidate.remove(field);
+                }
+            }
+
+            for (i, candidates) in field_candidates.iter_mut().enumerate() {
+                if candidates.is_empty() {
+                    continue;
+                } else if candidates.len() == 1 {
+                    for n in candidates.drain() {
+                        field2col.insert(n, i);
+                    }
+                }
+            }
+        }
+
+        let soln: u64 = field2col
+            .iter()
+            .map(|(name, &i)| match name.starts_with("departure") {
+                true => notes.my_ticket[i] as u64,
+                false => 1,
+            })
+            .product();
+
+        Ok(soln)
+    }
+}
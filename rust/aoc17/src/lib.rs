@@ -0,0 +1,77 @@
+use aoc_core::automaton::{self, Automaton, Backend, Dense, Rule, Sparse};
+use aoc_core::{Result, Solution};
+
+/// Boot cycles the pocket dimension runs before counting
+pub const CYCLES: usize = 6;
+
+/// Which storage to run the automaton on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Storage {
+    Sparse,
+    Dense,
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<&str> {
+        // Validate the slice up front; each part places it in its own dimension
+        automaton::from_plane::<2>(input)?;
+
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Result<usize> {
+        boot(input, 3, CYCLES, Storage::Dense)
+    }
+
+    fn part2(input: &&str) -> Result<usize> {
+        boot(input, 4, CYCLES, Storage::Dense)
+    }
+}
+
+fn run<const N: usize, B: Backend<N>>(input: &str, cycles: usize) -> Result<usize> {
+    let mut pocket: Automaton<N, B> = Automaton::new(Rule::conway(), automaton::from_plane(input)?);
+    pocket.run(cycles);
+
+    Ok(pocket.count())
+}
+
+/// Active cubes after `cycles` of Conway cubes in `dims` dimensions, starting
+/// from the 2D slice in `input`
+pub fn boot(input: &str, dims: usize, cycles: usize, storage: Storage) -> Result<usize> {
+    match (dims, storage) {
+        (2, Storage::Sparse) => run::<2, Sparse<2>>(input, cycles),
+        (3, Storage::Sparse) => run::<3, Sparse<3>>(input, cycles),
+        (4, Storage::Sparse) => run::<4, Sparse<4>>(input, cycles),
+        (5, Storage::Sparse) => run::<5, Sparse<5>>(input, cycles),
+        (6, Storage::Sparse) => run::<6, Sparse<6>>(input, cycles),
+        (2, Storage::Dense) => run::<2, Dense<2>>(input, cycles),
+        (3, Storage::Dense) => run::<3, Dense<3>>(input, cycles),
+        (4, Storage::Dense) => run::<4, Dense<4>>(input, cycles),
+        (5, Storage::Dense) => run::<5, Dense<5>>(input, cycles),
+        (6, Storage::Dense) => run::<6, Dense<6>>(input, cycles),
+        _ => Err(format!("can only boot 2 to 6 dimensions, not {}", dims).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_test() {
+        let input = include_str!("../examples/example1.txt");
+
+        for dims in 3..=4 {
+            assert_eq!(
+                boot(input, dims, CYCLES, Storage::Sparse).unwrap(),
+                boot(input, dims, CYCLES, Storage::Dense).unwrap()
+            );
+        }
+    }
+}
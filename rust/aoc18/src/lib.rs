@@ -0,0 +1,136 @@
+use aoc_core::{ParseError, Result, Solution};
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = &'a str;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<&str> {
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Result<u64> {
+        part1(input)
+    }
+
+    fn part2(input: &&str) -> Result<u64> {
+        part2(input)
+    }
+}
+
+peg::parser! {
+    grammar parser_p1() for str {
+        pub rule eval_expr() -> u64 = precedence!{
+                x:(@) _ "+" _ y:@ { x + y }
+                x:(@) _ "*" _ y:@ { x * y }
+                "(" _ e:eval_expr() _ ")" { e }
+                n:num() { n }
+        }
+
+        rule num() -> u64
+            = x:$(['0'..='9']+) {? x.parse().or(Err("a number that fits in u64")) }
+
+        rule _() = " "?
+    }
+}
+
+peg::parser! {
+    grammar parser_p2() for str {
+        pub rule eval_expr() -> u64 = precedence!{
+                x:(@) _ "*" _ y:@ { x * y }
+                --
+                x:(@) _ "+" _ y:@ { x + y }
+                --
+                "(" _ e:eval_expr() _ ")" { e }
+                n:num() { n }
+        }
+
+        rule num() -> u64
+            = x:$(['0'..='9']+) {? x.parse().or(Err("a number that fits in u64")) }
+
+        rule _() = " "?
+    }
+}
+
+type PegError = peg::error::ParseError<peg::str::LineCol>;
+
+fn expr_error(input: &str, line: &str, e: PegError) -> ParseError {
+    ParseError::within(input, line, e.location.offset, e.expected.to_string())
+}
+
+pub fn part1(input: &str) -> Result<u64> {
+    let soln: u64 = input
+        .lines()
+        .map(|l| {
+            let n = parser_p1::eval_expr(l).map_err(|e| expr_error(input, l, e))?;
+            Ok(n)
+        })
+        .sum::<Result<_>>()?;
+    Ok(soln)
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    let soln: u64 = input
+        .lines()
+        .map(|l| {
+            let n = parser_p2::eval_expr(l).map_err(|e| expr_error(input, l, e))?;
+            Ok(n)
+        })
+        .sum::<Result<_>>()?;
+    Ok(soln)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_test() {
+        let expr = "2 * 3 + (4 * 5)";
+        let soln = parser_p1::eval_expr(expr).unwrap();
+        assert!(soln == 26);
+
+        let expr = "5 + (8 * 3 + 9 + 3 * 4 * 3)";
+        let soln = parser_p1::eval_expr(expr).unwrap();
+        assert!(soln == 437);
+
+        let expr = "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))";
+        let soln = parser_p1::eval_expr(expr).unwrap();
+        assert!(soln == 12240);
+
+        let expr = "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+        let soln = parser_p1::eval_expr(expr).unwrap();
+        assert!(soln == 13632);
+    }
+
+    #[test]
+    fn part2_test() {
+        let expr = "1 + (2 * 3) + (4 * (5 + 6))";
+        let soln = parser_p2::eval_expr(expr).unwrap();
+        assert!(soln == 51);
+
+        let expr = "2 * 3 + (4 * 5)";
+        let soln = parser_p2::eval_expr(expr).unwrap();
+        assert!(soln == 46);
+
+        let expr = "5 + (8 * 3 + 9 + 3 * 4 * 3)";
+        let soln = parser_p2::eval_expr(expr).unwrap();
+        assert!(soln == 1445);
+
+        let expr = "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))";
+        let soln = parser_p2::eval_expr(expr).unwrap();
+        assert!(soln == 669060);
+
+        let expr = "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+        let soln = parser_p2::eval_expr(expr).unwrap();
+        assert!(soln == 23340);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let e = part2("1 + 2\n2 * (3 + x)\n").unwrap_err();
+        assert!(e.to_string().starts_with("line 2, column 10: expected"));
+    }
+}
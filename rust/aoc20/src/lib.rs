@@ -0,0 +1,457 @@
+use std::collections::{HashMap, HashSet};
+use std::iter::FromIterator;
+
+use aoc_core::grid::{Grid, Transform};
+use aoc_core::parse::split_once_at;
+use aoc_core::{ParseError, Result, Solution};
+
+peg::parser! {
+    grammar tile_parser() for str {
+        rule num() -> u32
+            = n:$(['0'..='9']+) {? n.parse().or(Err("a tile id")) }
+
+        rule _() = " "?
+
+        pub rule tile_line() -> u32
+            = "Tile" _ n:num() ":" { n }
+    }
+}
+
+#[derive(Debug)]
+struct Edges {
+    top: String,
+    bottom: String,
+    left: String,
+    right: String,
+}
+
+const TOP: usize = 0;
+const RIGHT: usize = 1;
+const BOTTOM: usize = 2;
+const LEFT: usize = 3;
+
+impl Edges {
+    fn from_image(img: &Grid<bool>) -> Edges {
+        let bits = |x: &bool| if *x { '1' } else { '0' };
+
+        Edges {
+            top: img.row(0).iter().map(bits).collect(),
+            bottom: img.row(img.height() - 1).iter().map(bits).collect(),
+            left: img.column(0).map(bits).collect(),
+            right: img.column(img.width() - 1).map(bits).collect(),
+        }
+    }
+
+    fn to_view(&self) -> Result<[u16; 4]> {
+        let top = u16::from_str_radix(&self.top, 2)?;
+        let bottom = u16::from_str_radix(&self.bottom, 2)?;
+        let left = u16::from_str_radix(&self.left, 2)?;
+        let right = u16::from_str_radix(&self.right, 2)?;
+
+        Ok([top, right, bottom, left])
+    }
+
+    fn rotate(&self) -> Edges {
+        Edges {
+            top: self.right.clone(),
+            bottom: self.left.clone(),
+            left: self.top.chars().rev().collect(),
+            right: self.bottom.chars().rev().collect(),
+        }
+    }
+
+    fn flip(&self) -> Edges {
+        Edges {
+            top: self.top.chars().rev().collect(),
+            bottom: self.bottom.chars().rev().collect(),
+            left: self.right.clone(),
+            right: self.left.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct Tile {
+    tid: u32,
+    views: [[u16; 4]; 8],
+    image: Grid<bool>,
+}
+
+#[derive(Debug)]
+struct PlacedTile<'a> {
+    tile: &'a Tile,
+    oid: usize,
+}
+
+fn parse_input(input: &str) -> Result<Vec<Tile>> {
+    let it = input.trim_end().split("\n\n");
+
+    let mut tiles = vec![];
+
+    for g in it {
+        let (header, body) = split_once_at(input, g, "\n")?;
+
+        let tid = tile_parser::tile_line(header).map_err(|e| {
+            ParseError::within(input, header, e.location.offset, e.expected.to_string())
+        })?;
+
+        let image = Grid::parse_within(input, body, "`#` or `.`", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+
+        if image.width() != image.height() {
+            let expected = format!("a square tile of {} rows", image.width());
+            return Err(ParseError::within(input, body, body.len(), expected).into());
+        }
+
+        if image.width() < 3 {
+            let expected = "a tile at least 3 wide, to keep something once its border is trimmed";
+            return Err(ParseError::at(input, body, expected).into());
+        }
+
+        let e0 = Edges::from_image(&image);
+        let e1 = e0.rotate();
+        let e2 = e1.rotate();
+        let e3 = e2.rotate();
+        let e4 = e0.flip();
+        let e5 = e4.rotate();
+        let e6 = e5.rotate();
+        let e7 = e6.rotate();
+
+        tiles.push(Tile {
+            tid,
+            views: [
+                e0.to_view()?,
+                e1.to_view()?,
+                e2.to_view()?,
+                e3.to_view()?,
+                e4.to_view()?,
+                e5.to_view()?,
+                e6.to_view()?,
+                e7.to_view()?,
+            ],
+            image,
+        });
+    }
+
+    Ok(tiles)
+}
+
+fn find_corners(tiles: &[Tile]) -> Vec<u32> {
+    // Create HashMap of edges and the tiles that contain that edge. Tiles
+    // with an unmatched edge have to be along the border.
+    let mut cnt: HashMap<u16, HashSet<u32>> = HashMap::new();
+    for tile in tiles {
+        let tid = tile.tid;
+        let v = &tile.views;
+
+        for s in v.iter().flatten() {
+            let e = cnt.entry(*s).or_default();
+            (*e).insert(tid);
+        }
+    }
+
+    let mut tile_unmatched_edges: HashMap<u32, HashSet<u16>> = HashMap::new();
+
+    for (en, tids) in &mut cnt {
+        if tids.len() == 1 {
+            if let Some(tidx) = tids.iter().next() {
+                let e = tile_unmatched_edges
+                    .entry(*tidx)
+                    .or_insert(HashSet::with_capacity(4));
+                (*e).insert(*en);
+            }
+        }
+    }
+
+    // Find tiles that have two unmatched edges. These are at the corners
+    // Since the flipped edges will also be unmatched, there will be 4 edges all together
+    let mut corner_tiles = vec![];
+
+    for (tidx, edges) in &tile_unmatched_edges {
+        if edges.len() == 4 {
+            corner_tiles.push(*tidx);
+        }
+    }
+
+    corner_tiles
+}
+
+fn get_starting_orientation(t: &Tile, tiles: &[Tile]) -> Result<usize> {
+    let mut other_en: HashSet<u16> = HashSet::new();
+
+    for ot in tiles.iter() {
+        if ot.tid == t.tid {
+            continue;
+        }
+
+        for s in ot.views.iter().flatten() {
+            other_en.insert(*s);
+        }
+    }
+
+    (0..8)
+        .find(|&oid| {
+            other_en.contains(&t.views[oid][RIGHT]) && other_en.contains(&t.views[oid][BOTTOM])
+        })
+        .ok_or_else(|| {
+            format!(
+                "no arrangement: corner tile {} has no neighbours to its right and below",
+                t.tid
+            )
+            .into()
+        })
+}
+
+type PuzzleSolution<'a> = HashMap<(u8, u8), PlacedTile<'a>>;
+
+fn solve_puzzle(tiles: &[Tile]) -> Result<PuzzleSolution<'_>> {
+    let ntiles = tiles.len();
+    let psize = (ntiles as f64).sqrt() as usize;
+
+    if psize * psize != ntiles {
+        return Err(format!("no arrangement: {} tiles do not make a square", ntiles).into());
+    }
+    if psize > u8::MAX as usize {
+        return Err(format!("no arrangement: {} tiles are too many to place", ntiles).into());
+    }
+
+    let tsize = tiles
+        .first()
+        .ok_or("no arrangement: there are no tiles")?
+        .image
+        .width();
+    if let Some(t) = tiles.iter().find(|t| t.image.width() != tsize) {
+        return Err(format!(
+            "no arrangement: tile {} is {} wide, not {}",
+            t.tid,
+            t.image.width(),
+            tsize
+        )
+        .into());
+    }
+
+    let corner_tiles_ids = find_corners(tiles);
+
+    let mut available_tiles: HashSet<&Tile> = HashSet::from_iter(tiles.iter());
+
+    let mut solution = HashMap::with_capacity(ntiles);
+
+    // Pick random corner piece in original orientation to start with
+    let t = corner_tiles_ids
+        .first()
+        .and_then(|&tid| tiles.iter().find(|&x| x.tid == tid))
+        .ok_or("no arrangement: there is no corner tile")?;
+
+    let starting_oid = get_starting_orientation(t, tiles)?;
+
+    solution.insert(
+        (0_u8, 0_u8),
+        PlacedTile {
+            tile: t,
+            oid: starting_oid,
+        },
+    );
+
+    available_tiles.remove(&t);
+
+    for i in 0..psize as u8 {
+        for j in 0..psize as u8 {
+            if (i, j) == (0, 0) {
+                continue;
+            }
+
+            // Edges of the tiles already placed to the left and above, which this one must match
+            let target_left = match j {
+                0 => None,
+                _ => solution
+                    .get(&(i, j - 1))
+                    .map(|l_pt| l_pt.tile.views[l_pt.oid][RIGHT]),
+            };
+            let target_up = match i {
+                0 => None,
+                _ => solution
+                    .get(&(i - 1, j))
+                    .map(|u_pt| u_pt.tile.views[u_pt.oid][BOTTOM]),
+            };
+
+            let fits = |view: &[u16; 4]| {
+                target_left.iter().all(|&l| view[LEFT] == l)
+                    && target_up.iter().all(|&u| view[TOP] == u)
+            };
+
+            let (mt, mt_oid) = available_tiles
+                .iter()
+                .find_map(|&candidate_tile| {
+                    (0..8)
+                        .find(|&oid| fits(&candidate_tile.views[oid]))
+                        .map(|oid| (candidate_tile, oid))
+                })
+                .ok_or_else(|| {
+                    format!("no arrangement: no tile fits at row {}, column {}", i, j)
+                })?;
+
+            solution.insert(
+                (i, j),
+                PlacedTile {
+                    tile: mt,
+                    oid: mt_oid,
+                },
+            );
+            available_tiles.remove(&mt);
+        }
+    }
+
+    Ok(solution)
+}
+
+fn assemble_image(soln: &PuzzleSolution) -> Grid<bool> {
+    // Trim and orient tiles
+    let img_pieces: HashMap<(u8, u8), Grid<bool>> = soln
+        .iter()
+        .map(|(key, pt)| {
+            let img = &pt.tile.image;
+            let trimmed = img.crop((1, 1), img.width() - 2, img.height() - 2);
+            let t = Transform::new(pt.oid >= 4, pt.oid as u8 % 4);
+
+            (*key, trimmed.transform(t))
+        })
+        .collect();
+
+    // Combine into single image
+    let psize = (soln.len() as f64).sqrt() as usize;
+    let tsize = img_pieces[&(0, 0)].width();
+    let size = psize * tsize;
+
+    let mut cells = Vec::with_capacity(size * size);
+
+    for i in 0..psize {
+        for ri in 0..tsize {
+            for j in 0..psize {
+                cells.extend_from_slice(img_pieces[&(i as u8, j as u8)].row(ri));
+            }
+        }
+    }
+
+    Grid::new(size, size, cells)
+}
+
+// Monster
+//                   #
+//#    ##    ##    ###
+// #  #  #  #  #  #
+const MONSTER: [(usize, usize); 15] = [
+    (1, 0),
+    (2, 1),
+    (2, 4),
+    (1, 5),
+    (1, 6),
+    (2, 7),
+    (2, 10),
+    (1, 11),
+    (1, 12),
+    (2, 13),
+    (2, 16),
+    (1, 17),
+    (0, 18),
+    (1, 18),
+    (1, 19),
+];
+
+const MONSTER_H: usize = 3;
+const MONSTER_W: usize = 20;
+
+fn find_monsters(img: &Grid<bool>) -> u32 {
+    let mut n_found = 0;
+
+    for &t in Transform::ALL.iter() {
+        let x = img.transform(t);
+        let xsz = x.height();
+
+        for i in 0..xsz.saturating_sub(MONSTER_H) {
+            for j in 0..xsz.saturating_sub(MONSTER_W) {
+                if MONSTER.iter().all(|(mi, mj)| x[(i + mi, j + mj)]) {
+                    n_found += 1;
+                }
+            }
+        }
+    }
+
+    n_found
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input<'a> = Vec<Tile>;
+    type Part1 = u64;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Tile>> {
+        parse_input(input)
+    }
+
+    fn part1(tiles: &Vec<Tile>) -> Result<u64> {
+        let corner_tiles_ids = find_corners(tiles);
+
+        if corner_tiles_ids.len() != 4 {
+            return Err(format!(
+                "no arrangement: {} tiles look like corners",
+                corner_tiles_ids.len()
+            )
+            .into());
+        }
+
+        corner_tiles_ids
+            .iter()
+            .try_fold(1u64, |p, &x| p.checked_mul(x as u64))
+            .ok_or_else(|| "the product of the corner tile ids is too large".into())
+    }
+
+    fn part2(tiles: &Vec<Tile>) -> Result<u32> {
+        let solved = solve_puzzle(tiles)?;
+
+        let aimg = assemble_image(&solved);
+
+        let hashes_per_monster: u32 = 15;
+        let number_of_monsters: u32 = find_monsters(&aimg);
+        let total_hashes = aimg.iter().filter(|&&x| x).count() as u32;
+
+        number_of_monsters
+            .checked_mul(hashes_per_monster)
+            .and_then(|monster_hashes| total_hashes.checked_sub(monster_hashes))
+            .ok_or_else(|| {
+                format!(
+                    "{} sea monsters overlap too much to fit in {} `#`s",
+                    number_of_monsters, total_hashes
+                )
+                .into()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_arrangement_test() {
+        let tile = |tid| format!("Tile {}:\n#..\n.#.\n..#\n", tid);
+
+        // A lone tile has no neighbours, so no corners either
+        let tiles = Day20::parse(&tile(1)).unwrap();
+        assert!(Day20::part1(&tiles).is_err());
+        assert!(Day20::part2(&tiles).is_err());
+
+        let tiles = Day20::parse(&[tile(1), tile(2), tile(3)].join("\n")).unwrap();
+        let e = Day20::part2(&tiles).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "no arrangement: 3 tiles do not make a square"
+        );
+
+        assert!(Day20::parse("Tile 1:\n#.\n.#\n").is_err());
+    }
+}
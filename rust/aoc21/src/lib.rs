@@ -0,0 +1,120 @@
+use std::collections::{HashMap, HashSet};
+use std::iter::FromIterator;
+
+use aoc_core::{ParseError, Result, Solution};
+
+peg::parser! {
+    grammar food_parser() for str {
+        rule word() -> &'input str
+            = w:$(['a'..='z']+) { w }
+
+        rule ingredients() -> Vec<&'input str>
+            = w:word() ++ " " { w }
+
+        rule allergens() -> Vec<&'input str>
+            = "(contains" _ w:word() ++ ", " ")" { w }
+
+        rule _() = " "?
+
+        pub rule parse_line() -> (HashSet<&'input str>, HashSet<&'input str>)
+            = i:ingredients() _ a:allergens() {
+                (HashSet::from_iter(i.iter().copied()),
+                 HashSet::from_iter(a.iter().copied())) }
+    }
+
+}
+
+#[derive(Debug, Default)]
+pub struct Food<'a> {
+    ingredients: HashSet<&'a str>,
+    allergens: HashSet<&'a str>,
+}
+
+fn parse_input(input: &str) -> Result<Vec<Food<'_>>> {
+    let mut foods = vec![];
+    for line in input.lines() {
+        let (i, a) = food_parser::parse_line(line).map_err(|e| {
+            ParseError::within(input, line, e.location.offset, e.expected.to_string())
+        })?;
+        foods.push(Food {
+            ingredients: i,
+            allergens: a,
+        });
+    }
+
+    Ok(foods)
+}
+
+fn identify_allergens<'a>(foods: &[Food<'a>]) -> Result<HashMap<&'a str, &'a str>> {
+    let mut allergen_candidates: HashMap<&str, HashSet<&str>> = HashMap::new();
+
+    for f in foods.iter() {
+        for a in f.allergens.iter() {
+            let e = allergen_candidates.entry(*a).or_default();
+            if e.is_empty() {
+                (*e).extend(&f.ingredients);
+            }
+            *e = e.intersection(&f.ingredients).copied().collect();
+        }
+    }
+
+    let mut a2i = HashMap::new();
+
+    while let Some((&a, _)) = allergen_candidates.iter().find(|(_, s)| s.len() == 1) {
+        if let Some(&i) = allergen_candidates[a].iter().next() {
+            a2i.insert(a, i);
+
+            for h in allergen_candidates.values_mut() {
+                h.remove(&i);
+            }
+        }
+    }
+
+    Ok(a2i)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input<'a> = Vec<Food<'a>>;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Vec<Food<'_>>> {
+        parse_input(input)
+    }
+
+    fn part1(foods: &Vec<Food<'_>>) -> Result<usize> {
+        let a2i = identify_allergens(foods)?;
+        let allergen_ingredients = a2i.values().collect::<HashSet<_>>();
+        let mut cnt = 0;
+
+        for f in foods.iter() {
+            cnt += f
+                .ingredients
+                .iter()
+                .filter(|&i| !allergen_ingredients.contains(&i))
+                .count();
+        }
+
+        Ok(cnt)
+    }
+
+    fn part2(foods: &Vec<Food<'_>>) -> Result<String> {
+        let a2i = identify_allergens(foods)?;
+
+        let mut sorted_allergens = a2i.keys().collect::<Vec<_>>();
+        sorted_allergens.sort();
+
+        let mut x = vec![];
+        for a in sorted_allergens.iter() {
+            if let Some(i) = a2i.get(*a) {
+                x.push(*i);
+            }
+        }
+
+        let soln = x.join(",");
+
+        Ok(soln)
+    }
+}
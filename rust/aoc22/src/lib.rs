@@ -0,0 +1,146 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use aoc_core::parse::parse_at;
+use aoc_core::{ParseError, Result, Solution};
+
+pub type Deck = VecDeque<u16>;
+
+fn parse_input(input: &str) -> Result<Vec<Deck>> {
+    let decks = input
+        .trim_end()
+        .split("\n\n")
+        .map(|d| {
+            let mut acc = VecDeque::new();
+            for line in d.lines() {
+                if line.starts_with("Player") {
+                    continue;
+                }
+
+                acc.push_back(parse_at(input, line, "a card number")?);
+            }
+            Ok(acc)
+        })
+        .collect::<Result<Vec<VecDeque<_>>>>()?;
+
+    if decks.len() != 2 {
+        return Err(ParseError::at_end(input, "exactly two players' decks").into());
+    }
+
+    Ok(decks)
+}
+
+fn score_deck(deck: &Deck) -> u64 {
+    deck.iter()
+        .rev()
+        .enumerate()
+        .map(|(i, c)| (*c as u64) * (i + 1) as u64)
+        .sum()
+}
+
+fn play_game(decks: &[Deck]) -> u64 {
+    let mut p1 = decks[0].clone();
+    let mut p2 = decks[1].clone();
+
+    while !p1.is_empty() && !p2.is_empty() {
+        let c1 = p1.pop_front().unwrap();
+        let c2 = p2.pop_front().unwrap();
+
+        if c1 > c2 {
+            p1.push_back(c1);
+            p1.push_back(c2);
+        } else {
+            p2.push_back(c2);
+            p2.push_back(c1);
+        }
+    }
+
+    if p1.is_empty() {
+        score_deck(&p2)
+    } else {
+        score_deck(&p1)
+    }
+}
+
+fn play_recursive_game(p1: &mut Deck, p2: &mut Deck) -> usize {
+    let mut decks_seen = HashSet::new();
+
+    while !p1.is_empty() && !p2.is_empty() {
+        if !decks_seen.insert(hash_decks(p1, p2)) {
+            return 1;
+        }
+
+        let c1 = p1.pop_front().unwrap();
+        let c2 = p2.pop_front().unwrap();
+
+        let winner = if (p1.len() >= c1 as usize) && (p2.len() >= c2 as usize) {
+            let mut np1 = p1.iter().take(c1 as usize).copied().collect();
+            let mut np2 = p2.iter().take(c2 as usize).copied().collect();
+
+            play_recursive_game(&mut np1, &mut np2)
+        } else if c1 > c2 {
+            1
+        } else {
+            2
+        };
+
+        match winner {
+            1 => {
+                p1.push_back(c1);
+                p1.push_back(c2)
+            }
+            2 => {
+                p2.push_back(c2);
+                p2.push_back(c1)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    if p1.is_empty() {
+        2
+    } else {
+        1
+    }
+}
+
+fn hash_decks(a: &Deck, b: &Deck) -> u64 {
+    let mut s = DefaultHasher::new();
+    a.hash(&mut s);
+    b.hash(&mut s);
+    s.finish()
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input<'a> = Vec<Deck>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Deck>> {
+        parse_input(input)
+    }
+
+    fn part1(decks: &Vec<Deck>) -> Result<u64> {
+        let score = play_game(decks);
+
+        Ok(score)
+    }
+
+    fn part2(decks: &Vec<Deck>) -> Result<u64> {
+        let mut p1 = decks[0].clone();
+        let mut p2 = decks[1].clone();
+
+        let winner = play_recursive_game(&mut p1, &mut p2);
+
+        let soln = match winner {
+            1 => score_deck(&p1),
+            2 => score_deck(&p2),
+            _ => unreachable!(),
+        };
+
+        Ok(soln)
+    }
+}
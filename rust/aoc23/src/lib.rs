@@ -0,0 +1,206 @@
+use aoc_core::{ParseError, Result, Solution};
+
+#[derive(Debug)]
+struct Game {
+    current: usize,
+    next_cup: Vec<usize>,
+}
+
+impl Game {
+    fn build_game(nums: &[usize], pad_to: usize) -> Game {
+        let current = nums[0];
+        let sz: usize = if let Some(m) = nums.iter().max() {
+            *m
+        } else {
+            unreachable!();
+        };
+
+        let addtl = (pad_to + 1) - (sz + 1);
+
+        let mut next_cup = vec![0; pad_to + 1];
+
+        for it in nums
+            .iter()
+            .cycle()
+            .take(sz + 1)
+            .copied()
+            .collect::<Vec<usize>>()
+            .windows(2)
+        {
+            next_cup[it[0]] = it[1]
+        }
+
+        if addtl > 0 {
+            next_cup[nums[nums.len() - 1]] = sz + 1;
+            for (k, next) in next_cup.iter_mut().enumerate().take(pad_to).skip(sz + 1) {
+                *next = k + 1;
+            }
+
+            next_cup[pad_to] = current;
+        }
+
+        Game { current, next_cup }
+    }
+
+    fn get_ordered_label(&self, start: usize) -> Vec<usize> {
+        let mut x = vec![];
+        x.push(start);
+
+        let mut i = start;
+        while self.next_cup[i] != start {
+            let a = self.next_cup[i];
+            x.push(a);
+            i = a;
+        }
+
+        x
+    }
+
+    fn get_dest(&self, a: usize, b: usize, c: usize) -> usize {
+        let mut t = self.current;
+        let sz = self.next_cup.len() - 1;
+
+        let mut x = (t + (sz - 1) - 1) % sz + 1;
+        while (a == x) || (b == x) || (c == x) {
+            t = x;
+            x = (t + (sz - 1) - 1) % sz + 1;
+        }
+
+        x
+    }
+
+    fn play_turn(&mut self) {
+        //
+        let a = self.next_cup[self.current];
+        let b = self.next_cup[a];
+        let c = self.next_cup[b];
+
+        let destination = self.get_dest(a, b, c);
+        // move cups
+        let cur_next = self.next_cup[c];
+        let dest_next = self.next_cup[destination];
+
+        self.next_cup[self.current] = cur_next;
+        self.next_cup[destination] = a;
+        self.next_cup[a] = b;
+        self.next_cup[b] = c;
+        self.next_cup[c] = dest_next;
+
+        self.current = self.next_cup[self.current]
+    }
+}
+
+/// The cup labels in order, which must be `1` to the number of cups, each
+/// used once
+fn parse_input(input: &str) -> Result<Vec<usize>> {
+    let mut x = Vec::new();
+    let mut labels = Vec::new();
+
+    for line in input.lines() {
+        for (i, c) in line.char_indices() {
+            let label = &line[i..i + c.len_utf8()];
+            let a = c
+                .to_digit(10)
+                .filter(|&a| a != 0)
+                .ok_or_else(|| ParseError::at(input, label, "a cup label `1` to `9`"))?;
+
+            if x.contains(&(a as usize)) {
+                return Err(ParseError::at(input, label, "a label not used already").into());
+            }
+            x.push(a as usize);
+            labels.push(label);
+        }
+    }
+
+    if x.is_empty() {
+        return Err(ParseError::at_end(input, "a cup label").into());
+    }
+
+    // Distinct labels from 1 with none missing
+    if let Some(i) = x.iter().position(|&a| a > x.len()) {
+        let expected = format!("a cup label `1` to `{}`, one per cup", x.len());
+        return Err(ParseError::at(input, labels[i], expected).into());
+    }
+
+    Ok(x)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = Vec<usize>;
+    type Part1 = String;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>> {
+        parse_input(input)
+    }
+
+    fn part1(nums: &Vec<usize>) -> Result<String> {
+        let mut game = Game::build_game(nums, 9);
+
+        for _ in 0..100 {
+            game.play_turn();
+        }
+
+        let soln = game
+            .get_ordered_label(1_usize)
+            .iter()
+            .skip(1)
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join("");
+
+        Ok(soln)
+    }
+
+    fn part2(nums: &Vec<usize>) -> Result<usize> {
+        let mut game = Game::build_game(nums, 1_000_000);
+
+        for _ in 0..10_000_000 {
+            game.play_turn();
+        }
+
+        let a = game.next_cup[1];
+        let b = game.next_cup[a];
+        let soln = a * b;
+
+        Ok(soln)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(parse_input("389125467\n").unwrap()[..3], [3, 8, 9]);
+
+        let errors = [
+            (
+                "",
+                "line 1, column 1: expected a cup label, found end of input",
+            ),
+            (
+                "3801\n",
+                "line 1, column 3: expected a cup label `1` to `9`, found `0`",
+            ),
+            (
+                "311\n",
+                "line 1, column 3: expected a label not used already, found `1`",
+            ),
+            (
+                "99\n",
+                "line 1, column 2: expected a label not used already, found `9`",
+            ),
+            (
+                "132\n4\n7\n",
+                "line 3, column 1: expected a cup label `1` to `5`, one per cup, found `7`",
+            ),
+        ];
+        for (input, message) in errors.iter() {
+            assert_eq!(parse_input(input).unwrap_err().to_string(), *message);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use aoc_core::automaton::{Automaton, Cell, Rule};
+use aoc_core::{ParseError, Result, Solution};
+
+// Excellent resource for hexagonal grids
+// https://www.redblobgames.com/grids/hexagons
+
+const OFFSETS: [Cell<3>; 6] = [
+    [1, -1, 0],
+    [-1, 1, 0],
+    [0, 1, -1],
+    [1, 0, -1],
+    [-1, 0, 1],
+    [0, -1, 1],
+];
+
+peg::parser! {
+    grammar hextile_offset_parser() for str {
+        rule east() -> (isize, isize, isize)
+            = x:$("e") { (1, -1, 0) }
+
+        rule west() -> (isize, isize, isize)
+            = x:$("w") { (-1, 1, 0) }
+
+        rule northwest() -> (isize, isize, isize)
+            = x:$("nw") { (0, 1, -1) }
+
+        rule northeast() -> (isize, isize, isize)
+            = x:$("ne") { (1, 0, -1) }
+
+        rule southwest() -> (isize, isize, isize)
+            = x:$("sw") { (-1, 0, 1) }
+
+        rule southeast() -> (isize, isize, isize)
+            = x:$("se") { (0, -1, 1) }
+
+        pub rule parse() -> Vec<(isize, isize, isize)>
+            = n:(northeast() / northwest() / southeast() / southwest() / east() / west()) ++ "" { n }
+
+    }
+}
+
+pub type Tiles = HashSet<Cell<3>>;
+
+fn parse_input(input: &str) -> Result<Tiles> {
+    let mut tiles = HashSet::new();
+
+    for line in input.lines() {
+        let moves = hextile_offset_parser::parse(line).map_err(|e| {
+            ParseError::within(input, line, e.location.offset, e.expected.to_string())
+        })?;
+
+        let t = moves.iter().fold([0, 0, 0], |mut acc, off| {
+            acc[0] += off.0;
+            acc[1] += off.1;
+            acc[2] += off.2;
+            acc
+        });
+
+        if !tiles.remove(&t) {
+            tiles.insert(t);
+        }
+    }
+
+    Ok(tiles)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input<'a> = Tiles;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Tiles> {
+        parse_input(input)
+    }
+
+    fn part1(tiles: &Tiles) -> Result<usize> {
+        let black_count = tiles.len();
+
+        Ok(black_count)
+    }
+
+    fn part2(tiles: &Tiles) -> Result<usize> {
+        // living art: a black tile with 0 or more than 2 black neighbours
+        // flips to white, a white tile with exactly 2 flips to black
+        let mut floor: Automaton<3> = Automaton::with_neighbourhood(
+            Rule::new(&[2], &[1, 2]),
+            OFFSETS.to_vec(),
+            tiles.iter().copied(),
+        );
+        floor.run(100);
+
+        let black_count = floor.count();
+        Ok(black_count)
+    }
+}
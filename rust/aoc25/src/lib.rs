@@ -0,0 +1,80 @@
+use aoc_core::parse::parse_at;
+use aoc_core::{ParseError, Result, Solution};
+
+fn find_loop_size(public_key: u64) -> u64 {
+    let mut loop_size = 0;
+    let mut value = 1;
+
+    while value != public_key {
+        value *= 7;
+        value %= 20201227;
+
+        loop_size += 1;
+    }
+
+    loop_size
+}
+
+fn get_enc_key(loop_size: u64, key: u64) -> u64 {
+    let mut value = 1;
+    for _ in 0..loop_size {
+        value *= key;
+        value %= 20201227;
+    }
+
+    value
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input<'a> = Vec<u64>;
+    type Part1 = u64;
+    type Part2 = &'static str;
+
+    fn parse(input: &str) -> Result<Vec<u64>> {
+        let public_keys = input
+            .lines()
+            .map(|x| parse_at(input, x, "a public key"))
+            .collect::<std::result::Result<Vec<u64>, _>>()?;
+
+        if public_keys.len() < 2 {
+            return Err(ParseError::at_end(input, "the card and door public keys").into());
+        }
+
+        Ok(public_keys)
+    }
+
+    fn part1(public_keys: &Vec<u64>) -> Result<u64> {
+        let card_loop_size = find_loop_size(public_keys[0]);
+        let door_loop_size = find_loop_size(public_keys[1]);
+
+        let card_enc = get_enc_key(door_loop_size, public_keys[0]);
+        let door_enc = get_enc_key(card_loop_size, public_keys[1]);
+
+        if card_enc != door_enc {
+            return Err(format!("encryption keys differ: {} {}", card_enc, door_enc).into());
+        }
+
+        Ok(card_enc)
+    }
+
+    fn part2(_: &Vec<u64>) -> Result<&'static str> {
+        // There is no second puzzle on the last day
+        Ok("none")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_test() {
+        assert_eq!(find_loop_size(5764801), 8);
+        assert_eq!(find_loop_size(17807724), 11);
+
+        assert_eq!(get_enc_key(11, 5764801), 14897079);
+        assert_eq!(get_enc_key(8, 17807724), 14897079);
+    }
+}
@@ -0,0 +1,46 @@
+use aoc_core::{Result, Solution};
+
+pub struct DayXX;
+
+impl Solution for DayXX {
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<&str> {
+        Ok(input)
+    }
+
+    fn part1(_input: &&str) -> Result<usize> {
+        Ok(0)
+    }
+
+    fn part2(_input: &&str) -> Result<usize> {
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/example.txt");
+
+    /// The example's answers from the puzzle text. The tests fail until they are filled in.
+    const PART1: Option<usize> = None;
+    const PART2: Option<usize> = None;
+
+    #[test]
+    fn part1_test() {
+        let expected = PART1.expect("record the example's part 1 answer in PART1");
+        let input = DayXX::parse(EXAMPLE).unwrap();
+        assert_eq!(DayXX::part1(&input).unwrap(), expected);
+    }
+
+    #[test]
+    fn part2_test() {
+        let expected = PART2.expect("record the example's part 2 answer in PART2");
+        let input = DayXX::parse(EXAMPLE).unwrap();
+        assert_eq!(DayXX::part2(&input).unwrap(), expected);
+    }
+}